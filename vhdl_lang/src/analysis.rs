@@ -9,7 +9,9 @@ mod analyze;
 mod concurrent;
 mod declarative;
 mod design_unit;
+mod lint;
 mod lock;
+mod metrics;
 mod named_entity;
 mod region;
 mod root;
@@ -20,4 +22,6 @@ mod visibility;
 #[cfg(test)]
 mod tests;
 
-pub use self::root::DesignRoot;
+pub(crate) use self::metrics::timed;
+pub use self::metrics::{Metrics, SourceMetrics, UnitMetrics, UnitTiming};
+pub use self::root::{DesignRoot, ResolutionStrictness, VHDLStandard};
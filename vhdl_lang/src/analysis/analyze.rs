@@ -15,6 +15,9 @@ use std::sync::Arc;
 pub enum AnalysisError {
     Fatal(CircularDependencyError),
     NotFatal(Diagnostic),
+    /// A name which could not be resolved but is not reported such as a name
+    /// selected from a missing primary unit with lenient resolution
+    Unknown,
 }
 
 impl AnalysisError {
@@ -68,6 +71,7 @@ impl AnalysisError {
                 diagnostics.push(diag);
                 Ok(())
             }
+            AnalysisError::Unknown => Ok(()),
         }
     }
 }
@@ -112,6 +116,18 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    pub fn lints_enabled(&self) -> bool {
+        self.root.lints_enabled()
+    }
+
+    pub fn resolution_strictness(&self) -> ResolutionStrictness {
+        self.root.resolution_strictness()
+    }
+
+    pub fn standard(&self) -> VHDLStandard {
+        self.root.standard()
+    }
+
     pub fn work_library_name(&self) -> &Symbol {
         self.current_unit.library_name()
     }
@@ -165,7 +181,12 @@ impl<'a> AnalyzeContext<'a> {
         library_name: &Symbol,
         region: &mut Region<'_>,
     ) -> FatalNullResult {
-        let units = self.root.get_library_units(library_name).unwrap();
+        // A library which is not loaded has no units with lenient resolution
+        let units = if let Some(units) = self.root.get_library_units(library_name) {
+            units
+        } else {
+            return Ok(());
+        };
 
         for unit in units.values() {
             match unit.kind() {
@@ -299,6 +320,11 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
+        if self.resolution_strictness() == ResolutionStrictness::Lenient {
+            // Assume the missing primary unit exists in a library that was not loaded
+            return Err(AnalysisError::Unknown);
+        }
+
         Err(AnalysisError::NotFatal(Diagnostic::error(
             pos,
             format!(
@@ -321,6 +347,16 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 
+    /// The analysis of std.standard unless it is the current unit or the std library is missing
+    pub fn standard_package_analysis(&self) -> Option<UnitReadGuard<'a>> {
+        if self.is_standard_package() {
+            return None;
+        }
+        let unit =
+            self.get_primary_unit_kind(&self.std_sym, &self.standard_sym, PrimaryKind::Package)?;
+        self.get_analysis(None, unit).ok()
+    }
+
     pub fn get_primary_analysis(
         &self,
         use_pos: &SrcPos,
@@ -12,6 +12,7 @@ use crate::ast::*;
 use crate::data::*;
 use analyze::*;
 use region::*;
+use root::*;
 
 impl<'a> AnalyzeContext<'a> {
     pub fn analyze_concurrent_part(
@@ -69,7 +70,20 @@ impl<'a> AnalyzeContext<'a> {
                     match sensitivity_list {
                         SensitivityList::Names(names) => {
                             for name in names.iter_mut() {
-                                self.resolve_name(parent, &name.pos, &mut name.item, diagnostics)?;
+                                if let Some(ent) = self
+                                    .resolve_name(parent, &name.pos, &mut name.item, diagnostics)?
+                                    .and_then(|resolved| resolved.into_non_overloaded())
+                                {
+                                    if !is_signal_or_unknown(ent.actual_kind()) {
+                                        diagnostics.error(
+                                            &name.pos,
+                                            format!(
+                                                "Sensitivity list entry '{}' is not a signal",
+                                                ent.designator()
+                                            ),
+                                        );
+                                    }
+                                }
                             }
                         }
                         SensitivityList::All => {}
@@ -77,6 +91,14 @@ impl<'a> AnalyzeContext<'a> {
                 }
                 let mut region = parent.nested();
                 self.analyze_declarative_part(&mut region, decl, diagnostics)?;
+                let process_label: Option<WithPos<Designator>> =
+                    statement.label.clone().map(|label| label.into());
+                self.define_labels_for_sequential_part(
+                    &mut region,
+                    statements,
+                    process_label.as_ref(),
+                    diagnostics,
+                );
                 self.analyze_sequential_part(&mut region, statements, diagnostics)?;
             }
             ConcurrentStatement::ForGenerate(ref mut gen) => {
@@ -85,11 +107,25 @@ impl<'a> AnalyzeContext<'a> {
                     discrete_range,
                     body,
                 } = gen;
-                self.analyze_discrete_range(parent, discrete_range, diagnostics)?;
+                let typ = self.analyze_discrete_range(parent, discrete_range, diagnostics)?;
+                if let DiscreteRange::Discrete(..) = discrete_range {
+                    if typ.is_none() {
+                        diagnostics.error(
+                            &index_name,
+                            format!(
+                                "Cannot determine type of generate parameter '{}'",
+                                index_name.item
+                            ),
+                        );
+                    }
+                }
                 let mut region = parent.nested();
                 region.add(
                     index_name.clone(),
-                    NamedEntityKind::Object(ObjectClass::Constant),
+                    NamedEntityKind::Object(Object {
+                        class: ObjectClass::Constant,
+                        subtype: typ.map(Subtype::new),
+                    }),
                     diagnostics,
                 );
                 self.analyze_generate_body(&mut region, body, diagnostics)?;
@@ -117,7 +153,7 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             ConcurrentStatement::Instance(ref mut instance) => {
-                self.analyze_instance(parent, instance, diagnostics)?;
+                self.analyze_instance(parent, statement.label.as_ref(), instance, diagnostics)?;
             }
             ConcurrentStatement::Assignment(ref mut assign) => {
                 // @TODO more delaymechanism
@@ -178,6 +214,7 @@ impl<'a> AnalyzeContext<'a> {
     fn analyze_instance(
         &self,
         parent: &Region<'_>,
+        label: Option<&Ident>,
         instance: &mut InstantiationStatement,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
@@ -236,6 +273,267 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_assoc_elems(parent, &mut instance.generic_map, diagnostics)?;
         self.analyze_assoc_elems(parent, &mut instance.port_map, diagnostics)?;
 
+        if let InstantiatedUnit::Entity(ref entity_name, ..) = instance.unit {
+            if let Some(data) =
+                self.lookup_library_unit(parent, entity_name, PrimaryKind::Entity)?
+            {
+                if let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) = *data {
+                    let generics = entity.generic_clause.as_deref().unwrap_or(&[]);
+                    let ports = entity.port_clause.as_deref().unwrap_or(&[]);
+                    let unit = format!("entity '{}'", entity.ident.item);
+                    check_associations(
+                        &unit,
+                        "generic",
+                        generics,
+                        &instance.generic_map,
+                        &entity_name.pos,
+                        diagnostics,
+                    );
+                    check_associations(
+                        &unit,
+                        "port",
+                        ports,
+                        &instance.port_map,
+                        &entity_name.pos,
+                        diagnostics,
+                    );
+                    if let Some(label) = label {
+                        check_output_actuals(label, ports, &instance.port_map, diagnostics);
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// The declaration of a primary unit referenced as 'lib.name'
+    pub fn lookup_library_unit(
+        &self,
+        parent: &Region<'_>,
+        unit_name: &WithPos<SelectedName>,
+        kind: PrimaryKind,
+    ) -> FatalResult<Option<UnitReadGuard<'a>>> {
+        let (prefix, suffix) = match unit_name.item {
+            SelectedName::Selected(ref prefix, ref suffix) => (prefix, suffix),
+            SelectedName::Designator(..) => return Ok(None),
+        };
+
+        let library = match prefix.item {
+            SelectedName::Designator(ref designator) => parent
+                .lookup_within(&prefix.pos, designator.designator())
+                .ok()
+                .and_then(|visible| visible.into_non_overloaded().ok()),
+            SelectedName::Selected(..) => None,
+        };
+
+        let library_name = match library.as_ref().map(|ent| (ent, ent.kind())) {
+            Some((ent, NamedEntityKind::Library)) => ent.designator().expect_identifier(),
+            _ => return Ok(None),
+        };
+
+        let name = if let Designator::Identifier(ref name) = suffix.designator() {
+            name
+        } else {
+            return Ok(None);
+        };
+
+        match self.get_primary_analysis(&suffix.pos, library_name, name, kind) {
+            Some(data) => data.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Only objects that are known not to be signals are rejected
+fn is_signal_or_unknown(kind: &NamedEntityKind) -> bool {
+    match kind {
+        NamedEntityKind::Object(object) => object.class == ObjectClass::Signal,
+        NamedEntityKind::InterfaceObject(object) => object.class == ObjectClass::Signal,
+        NamedEntityKind::DeferredConstant
+        | NamedEntityKind::File
+        | NamedEntityKind::InterfaceFile(..) => false,
+        _ => true,
+    }
+}
+
+/// Check that all required formals are associated and that there are no extra associations
+pub fn check_associations(
+    unit: &str,
+    kind: &str,
+    formals: &[InterfaceDeclaration],
+    elems: &[AssociationElement],
+    pos: &SrcPos,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut associated = vec![false; formals.len()];
+    let mut all_formals_known = true;
+    let mut positional = 0;
+
+    for elem in elems.iter() {
+        let idx = if let Some(ref formal) = elem.formal {
+            let candidates = formal_designators(&formal.item);
+            let idx = formals
+                .iter()
+                .position(|decl| candidates.contains(&interface_designator(decl)));
+
+            if idx.is_none() {
+                if let Name::Designator(ref designator) = formal.item {
+                    diagnostics.error(
+                        formal,
+                        format!("No {} '{}' within {}", kind, designator.designator(), unit),
+                    );
+                } else {
+                    // Could be a conversion function we do not understand
+                    all_formals_known = false;
+                }
+            }
+            idx
+        } else {
+            positional += 1;
+            if positional > formals.len() {
+                diagnostics.error(
+                    &elem.actual,
+                    format!(
+                        "Too many {} associations, {} has {}",
+                        kind,
+                        unit,
+                        formals.len()
+                    ),
+                );
+                None
+            } else {
+                Some(positional - 1)
+            }
+        };
+
+        if let Some(idx) = idx {
+            if elem.actual.item != ActualPart::Open {
+                associated[idx] = true;
+            }
+        }
+    }
+
+    if !all_formals_known {
+        return;
+    }
+
+    for (decl, is_associated) in formals.iter().zip(associated) {
+        if !is_associated && is_required(decl) {
+            diagnostics.error(
+                pos,
+                format!(
+                    "Missing association for {} '{}'",
+                    kind,
+                    interface_designator(decl)
+                ),
+            );
+        }
+    }
+}
+
+/// Check that the same signal is not the actual of several output ports of one instance
+fn check_output_actuals(
+    label: &Ident,
+    ports: &[InterfaceDeclaration],
+    elems: &[AssociationElement],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut driven: Vec<&SrcPos> = Vec::new();
+
+    for (positional, elem) in elems.iter().enumerate() {
+        let formal = if let Some(ref formal) = elem.formal {
+            // Partial formals and conversion functions are not checked
+            if let Name::Designator(ref designator) = formal.item {
+                ports
+                    .iter()
+                    .find(|decl| &interface_designator(decl) == designator.designator())
+            } else {
+                None
+            }
+        } else {
+            ports.get(positional)
+        };
+
+        // Several inout ports on the same resolved signal form a bus and are allowed
+        let is_output = match formal {
+            Some(InterfaceDeclaration::Object(ref object)) => object.mode == Mode::Out,
+            _ => false,
+        };
+
+        if !is_output {
+            continue;
+        }
+
+        // Only whole signals are considered, slices may drive disjoint elements
+        if let ActualPart::Expression(Expression::Name(ref name)) = elem.actual.item {
+            if let Name::Designator(ref designator) = name.as_ref() {
+                if let Some(ref decl_pos) = designator.reference {
+                    if driven.contains(&decl_pos) {
+                        diagnostics.error(
+                            &elem.actual,
+                            format!(
+                                "Signal '{}' drives multiple output ports of instance '{}'",
+                                designator.designator(),
+                                label.item
+                            ),
+                        );
+                    } else {
+                        driven.push(decl_pos);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A formal without default value which must be associated
+fn is_required(decl: &InterfaceDeclaration) -> bool {
+    match decl {
+        InterfaceDeclaration::Object(ref object) => {
+            // Only ports of mode in must be associated
+            object.expression.is_none()
+                && (object.class != ObjectClass::Signal || object.mode == Mode::In)
+        }
+        InterfaceDeclaration::File(..) => false,
+        InterfaceDeclaration::Type(..) | InterfaceDeclaration::Package(..) => true,
+        InterfaceDeclaration::Subprogram(_, ref default) => default.is_none(),
+    }
+}
+
+fn interface_designator(decl: &InterfaceDeclaration) -> Designator {
+    match decl {
+        InterfaceDeclaration::Object(ref object) => object.ident.item.clone().into(),
+        InterfaceDeclaration::File(ref file) => file.ident.item.clone().into(),
+        InterfaceDeclaration::Type(ref typ) => typ.ident.item.clone().into(),
+        InterfaceDeclaration::Subprogram(ref subpgm, ..) => subpgm.designator().item,
+        InterfaceDeclaration::Package(ref package) => package.ident.item.clone().into(),
+    }
+}
+
+/// The designators that a formal part could refer to,
+/// either as a (partial) formal or as the argument of a conversion function
+fn formal_designators(name: &Name) -> Vec<Designator> {
+    match name {
+        Name::Designator(ref designator) => vec![designator.designator().clone()],
+        Name::Selected(ref prefix, ..)
+        | Name::Indexed(ref prefix, ..)
+        | Name::Slice(ref prefix, ..) => formal_designators(&prefix.item),
+        Name::FunctionCall(ref fcall) => {
+            let mut designators = formal_designators(&fcall.name.item);
+            if let [AssociationElement {
+                formal: None,
+                actual:
+                    WithPos {
+                        item: ActualPart::Expression(Expression::Name(ref name)),
+                        ..
+                    },
+            }] = fcall.parameters.as_slice()
+            {
+                designators.extend(formal_designators(name));
+            }
+            designators
+        }
+        _ => Vec::new(),
+    }
 }
@@ -10,8 +10,9 @@ use crate::ast::*;
 use crate::data::*;
 use analyze::*;
 use fnv::FnvHashMap;
-use named_entity::Signature;
+use named_entity::{ScalarClass, Signature};
 use region::*;
+use semantic::is_static_null_range;
 use std::collections::hash_map::Entry;
 use std::sync::Arc;
 
@@ -107,15 +108,39 @@ impl<'a> AnalyzeContext<'a> {
             signature,
         } = alias;
 
+        if let Name::Designator(ref aliased) = name.item {
+            if aliased.designator() == &designator.item {
+                diagnostics.error(
+                    &designator.pos,
+                    format!("Alias '{}' shadows itself", designator.item),
+                );
+                return Ok(None);
+            }
+        }
+
         let resolved_name = self.resolve_name(region, &name.pos, &mut name.item, diagnostics)?;
 
-        if let Some(ref mut subtype_indication) = subtype_indication {
+        let alias_subtype = if let Some(ref mut subtype_indication) = subtype_indication {
             // Object alias
-            self.analyze_subtype_indication(region, subtype_indication, diagnostics)?;
-        }
+            match self.resolve_subtype_indication(region, subtype_indication, diagnostics) {
+                Ok(subtype) => Some((subtype_indication.type_mark.pos.clone(), subtype)),
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
         let kind = {
             if subtype_indication.is_some() {
+                if let (Some((pos, subtype)), Some(NamedEntities::Single(ent))) = (
+                    alias_subtype,
+                    resolved_name.and_then(|resolved_name| resolved_name.into_known()),
+                ) {
+                    check_alias_subtype(&pos, &subtype, &ent, diagnostics);
+                }
                 NamedEntityKind::OtherAlias
             } else if let Some(resolved_name) = resolved_name {
                 if let Some(named_entities) = resolved_name.into_known() {
@@ -186,6 +211,63 @@ impl<'a> AnalyzeContext<'a> {
         ))))
     }
 
+    /// Resolve the uninstantiated subprogram of a subprogram instantiation
+    fn analyze_subprogram_instance_name(
+        &self,
+        region: &Region<'_>,
+        instance: &mut SubprogramInstantiation,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult<Option<Arc<NamedEntity>>> {
+        let SubprogramInstantiation {
+            subprogram_name,
+            signature,
+            ..
+        } = instance;
+
+        let overloaded = match self.resolve_selected_name(region, subprogram_name) {
+            Ok(NamedEntities::Overloaded(overloaded)) => overloaded,
+            Ok(NamedEntities::Single(ent)) => {
+                diagnostics.error(
+                    &subprogram_name.pos,
+                    format!("Expected subprogram, got {}", ent.describe()),
+                );
+                return Ok(None);
+            }
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                return Ok(None);
+            }
+        };
+
+        if let Some(ref mut signature) = signature {
+            match self.resolve_signature(region, signature) {
+                Ok(signature_key) => {
+                    if let Some(ent) = overloaded.get(&signature_key) {
+                        Ok(Some(ent))
+                    } else {
+                        diagnostics.error(
+                            &subprogram_name.pos,
+                            "Could not find declaration with given signature",
+                        );
+                        Ok(None)
+                    }
+                }
+                Err(err) => {
+                    err.add_to(diagnostics)?;
+                    Ok(None)
+                }
+            }
+        } else if overloaded.entities().count() == 1 {
+            Ok(Some(overloaded.first().clone()))
+        } else {
+            diagnostics.error(
+                &subprogram_name.pos,
+                "Signature required for instantiation of overloaded subprogram",
+            );
+            Ok(None)
+        }
+    }
+
     fn analyze_declaration(
         &self,
         region: &mut Region<'_>,
@@ -200,17 +282,24 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             Declaration::Object(ref mut object_decl) => {
-                self.analyze_subtype_indication(
+                let subtype = match self.resolve_subtype_indication(
                     region,
                     &mut object_decl.subtype_indication,
                     diagnostics,
-                )?;
+                ) {
+                    Ok(subtype) => Some(subtype),
+                    Err(err) => {
+                        err.add_to(diagnostics)?;
+                        None
+                    }
+                };
                 if let Some(ref mut expr) = object_decl.expression {
                     self.analyze_expression(region, expr, diagnostics)?;
+                    check_null_aggregate(&object_decl.subtype_indication, expr, diagnostics);
                 }
                 region.add(
                     &object_decl.ident,
-                    NamedEntityKind::from_object_declaration(object_decl),
+                    NamedEntityKind::from_object_declaration(object_decl, subtype),
                     diagnostics,
                 );
             }
@@ -227,6 +316,14 @@ impl<'a> AnalyzeContext<'a> {
                 }
                 if let Some(ref mut expr) = file_name {
                     self.analyze_expression(region, expr, diagnostics)?;
+                } else if open_info.is_some() {
+                    diagnostics.error(
+                        &ident.pos,
+                        format!(
+                            "File '{}' requires a logical name for this open mode",
+                            ident.item
+                        ),
+                    );
                 }
                 region.add(ident.clone(), NamedEntityKind::File, diagnostics);
             }
@@ -244,8 +341,9 @@ impl<'a> AnalyzeContext<'a> {
                     }
                     region.add(&attr_decl.ident, NamedEntityKind::Attribute, diagnostics);
                 }
-                // @TODO Ignored for now
-                Attribute::Specification(..) => {}
+                Attribute::Specification(ref attr_spec) => {
+                    check_attribute_entity_class(region, attr_spec, diagnostics);
+                }
             },
             Declaration::SubprogramBody(ref mut body) => {
                 let mut subpgm_region = region.nested();
@@ -279,6 +377,12 @@ impl<'a> AnalyzeContext<'a> {
                 )?;
                 subpgm_region.close(diagnostics);
 
+                self.define_labels_for_sequential_part(
+                    &mut subpgm_region,
+                    &body.statements,
+                    Some(&body.specification.designator()),
+                    diagnostics,
+                );
                 self.analyze_sequential_part(
                     &mut subpgm_region,
                     &mut body.statements,
@@ -304,20 +408,58 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
 
+            Declaration::SubprogramInstantiation(ref mut instance) => {
+                if let Some(ent) =
+                    self.analyze_subprogram_instance_name(region, instance, diagnostics)?
+                {
+                    if let Some(signature) = ent.signature() {
+                        region.add(
+                            instance.designator(),
+                            NamedEntityKind::Subprogram(signature.clone()),
+                            diagnostics,
+                        );
+                    }
+                }
+                if let Some(ref mut generic_map) = instance.generic_map {
+                    self.analyze_assoc_elems(region, generic_map, diagnostics)?;
+                }
+            }
+
             Declaration::Use(ref mut use_clause) => {
                 self.analyze_use_clause(region, &mut use_clause.item, diagnostics)?;
             }
 
             Declaration::Package(ref mut instance) => {
                 match self.analyze_package_instance_name(region, &mut instance.package_name) {
-                    Ok(package_region) => region.add(
-                        &instance.ident,
-                        NamedEntityKind::LocalPackageInstance(package_region),
-                        diagnostics,
-                    ),
+                    Ok(package_region) => {
+                        self.check_package_generic_map(region, instance, diagnostics)?;
+                        region.add(
+                            &instance.ident,
+                            NamedEntityKind::LocalPackageInstance(package_region),
+                            diagnostics,
+                        )
+                    }
                     Err(err) => err.add_to(diagnostics)?,
                 }
             }
+            Declaration::GroupTemplate(ref template) => {
+                region.add(&template.ident, NamedEntityKind::GroupTemplate, diagnostics);
+            }
+            Declaration::Group(ref mut group) => {
+                if let Err(err) = self.resolve_non_overloaded(
+                    region,
+                    &mut group.template_name,
+                    &|kind| match kind {
+                        NamedEntityKind::GroupTemplate => true,
+                        _ => false,
+                    },
+                    "group template",
+                ) {
+                    err.add_to(diagnostics)?;
+                }
+                // @TODO constituents must match the entity classes of the template
+                region.add(&group.ident, NamedEntityKind::Group, diagnostics);
+            }
             Declaration::Configuration(..) => {}
             Declaration::Type(..) => unreachable!("Handled elsewhere"),
         };
@@ -339,7 +481,7 @@ impl<'a> AnalyzeContext<'a> {
                 let enum_type = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::ScalarType(ScalarClass::Enumeration, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -364,7 +506,10 @@ impl<'a> AnalyzeContext<'a> {
                 //       the enum literals will not contain the full type declaration of the
                 //       enum type
                 parent.add_named_entity(
-                    Arc::new(enum_type.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(enum_type.clone_with_kind(NamedEntityKind::ScalarType(
+                        ScalarClass::Enumeration,
+                        implicit,
+                    ))),
                     diagnostics,
                 );
             }
@@ -447,6 +592,16 @@ impl<'a> AnalyzeContext<'a> {
                                 }
                             }
                         }
+                        ProtectedTypeDeclarativeItem::Use(ref mut use_clause) => {
+                            self.analyze_use_clause(
+                                &mut region,
+                                &mut use_clause.item,
+                                diagnostics,
+                            )?;
+                        }
+                        ProtectedTypeDeclarativeItem::AttributeSpecification(ref attr_spec) => {
+                            check_attribute_entity_class(&region, attr_spec, diagnostics);
+                        }
                     }
                 }
                 let region = region.without_parent();
@@ -461,7 +616,33 @@ impl<'a> AnalyzeContext<'a> {
             TypeDefinition::Record(ref mut element_decls) => {
                 let mut region = Region::default();
                 for elem_decl in element_decls.iter_mut() {
-                    self.analyze_subtype_indication(parent, &mut elem_decl.subtype, diagnostics)?;
+                    match self.resolve_subtype_indication(
+                        parent,
+                        &mut elem_decl.subtype,
+                        diagnostics,
+                    ) {
+                        Ok(subtype) => {
+                            // Unconstrained record elements are allowed since VHDL-2008
+                            if self.standard() == VHDLStandard::VHDL1993
+                                && elem_decl.subtype.constraint.is_none()
+                                && match subtype.base().actual_kind() {
+                                    NamedEntityKind::ArrayType(true, _) => true,
+                                    _ => false,
+                                }
+                            {
+                                diagnostics.error(
+                                    &elem_decl.ident,
+                                    format!(
+                                        "Record element '{}' must have a constrained subtype",
+                                        elem_decl.ident
+                                    ),
+                                );
+                            }
+                        }
+                        Err(err) => {
+                            err.add_to(diagnostics)?;
+                        }
+                    }
                     region.add(&elem_decl.ident, NamedEntityKind::RecordField, diagnostics);
                 }
                 region.close(diagnostics);
@@ -469,7 +650,7 @@ impl<'a> AnalyzeContext<'a> {
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::RecordType,
                     Some(&type_decl.ident.pos),
                 ));
                 parent.add_named_entity(type_ent, diagnostics);
@@ -491,10 +672,14 @@ impl<'a> AnalyzeContext<'a> {
                 }
                 self.analyze_subtype_indication(parent, subtype_indication, diagnostics)?;
 
+                let unbounded = array_indexes.iter().any(|index| match index {
+                    ArrayIndex::IndexSubtypeDefintion(..) => true,
+                    _ => false,
+                });
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::ArrayType(unbounded, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -506,7 +691,9 @@ impl<'a> AnalyzeContext<'a> {
                     implicit.push(Arc::downgrade(&to_string));
                 }
                 parent.add_named_entity(
-                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(
+                        type_ent.clone_with_kind(NamedEntityKind::ArrayType(unbounded, implicit)),
+                    ),
                     diagnostics,
                 );
             }
@@ -543,7 +730,7 @@ impl<'a> AnalyzeContext<'a> {
                 add_or_overwrite(
                     parent,
                     &type_decl.ident,
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::ScalarType(ScalarClass::Physical, Vec::new()),
                     overwrite_id,
                     diagnostics,
                 );
@@ -554,10 +741,15 @@ impl<'a> AnalyzeContext<'a> {
 
             TypeDefinition::Integer(ref mut range) => {
                 self.analyze_range(parent, range, diagnostics)?;
+                let class = if is_floating_range(range) {
+                    ScalarClass::Floating
+                } else {
+                    ScalarClass::Integer
+                };
                 let type_ent = Arc::new(NamedEntity::new_with_opt_id(
                     overwrite_id,
                     type_decl.ident.name().clone(),
-                    NamedEntityKind::TypeDeclaration(Vec::new()),
+                    NamedEntityKind::ScalarType(class, Vec::new()),
                     Some(&type_decl.ident.pos),
                 ));
 
@@ -569,7 +761,9 @@ impl<'a> AnalyzeContext<'a> {
                     implicit.push(Arc::downgrade(&to_string));
                 }
                 parent.add_named_entity(
-                    Arc::new(type_ent.clone_with_kind(NamedEntityKind::TypeDeclaration(implicit))),
+                    Arc::new(
+                        type_ent.clone_with_kind(NamedEntityKind::ScalarType(class, implicit)),
+                    ),
                     diagnostics,
                 );
             }
@@ -801,11 +995,21 @@ impl<'a> AnalyzeContext<'a> {
                     Some(&object_decl.ident.pos),
                 )
             }
-            InterfaceDeclaration::Type(ref ident) => NamedEntity::new(
-                ident.name().clone(),
-                NamedEntityKind::InterfaceType,
-                Some(&ident.pos),
-            ),
+            InterfaceDeclaration::Type(ref mut type_decl) => {
+                if let Some(TypeDefinition::Array(ref mut indexes, ref mut elem_subtype)) =
+                    type_decl.def
+                {
+                    for index in indexes.iter_mut() {
+                        self.analyze_array_index(region, index, diagnostics)?;
+                    }
+                    self.analyze_subtype_indication(region, elem_subtype, diagnostics)?;
+                }
+                NamedEntity::new(
+                    type_decl.ident.name().clone(),
+                    NamedEntityKind::InterfaceType,
+                    Some(&type_decl.ident.pos),
+                )
+            }
             InterfaceDeclaration::Subprogram(ref mut subpgm, ..) => {
                 let mut subpgm_region = region.nested();
                 let signature =
@@ -877,7 +1081,7 @@ impl<'a> AnalyzeContext<'a> {
 
     fn analyze_array_index(
         &self,
-        region: &mut Region<'_>,
+        region: &Region<'_>,
         array_index: &mut ArrayIndex,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
@@ -945,12 +1149,23 @@ impl<'a> AnalyzeContext<'a> {
         } = subtype_indication;
 
         let base_type = self.resolve_type_mark(region, type_mark)?;
+        let subtype = Subtype::new(base_type);
 
         if let Some(constraint) = constraint {
             self.analyze_subtype_constraint(region, &mut constraint.item, diagnostics)?;
+
+            if !is_compatible_constraint(subtype.base_type(), &constraint.item) {
+                diagnostics.push(Diagnostic::error(
+                    &constraint.pos,
+                    format!(
+                        "Incompatible constraint for type '{}'",
+                        subtype.base().designator()
+                    ),
+                ));
+            }
         }
 
-        Ok(Subtype::new(base_type))
+        Ok(subtype)
     }
 
     pub fn analyze_subtype_indication(
@@ -987,6 +1202,21 @@ impl<'a> AnalyzeContext<'a> {
     }
 }
 
+/// False for constraints which can never apply to the base type such as an index constraint on a scalar type
+/// @TODO constraints of array types are not checked yet
+fn is_compatible_constraint(base_type: &NamedEntity, constraint: &SubtypeConstraint) -> bool {
+    let is_range = match constraint {
+        SubtypeConstraint::Range(..) => true,
+        _ => false,
+    };
+
+    match base_type.actual_kind() {
+        NamedEntityKind::ScalarType(..) => is_range,
+        NamedEntityKind::RecordType => !is_range,
+        _ => true,
+    }
+}
+
 fn find_full_type_definition<'a>(
     name: &Symbol,
     decls: &'a [Declaration],
@@ -1024,3 +1254,189 @@ fn add_or_overwrite(
     region.add_named_entity(ent.clone(), diagnostics);
     ent
 }
+
+/// Check that the subtype of an object alias is compatible with the type of the aliased object
+fn check_alias_subtype(
+    pos: &SrcPos,
+    subtype: &Subtype,
+    aliased: &NamedEntity,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let object_subtype = match aliased.actual_kind() {
+        NamedEntityKind::Object(ref object) => object.subtype.as_ref(),
+        NamedEntityKind::InterfaceObject(ref object) => Some(&object.subtype),
+        _ => None,
+    };
+
+    if let Some(object_subtype) = object_subtype {
+        // @TODO only scalar versus composite types are distinguished for now
+        let is_scalar = |subtype: &Subtype| subtype.base().base_type().kind().is_scalar_type();
+
+        if is_scalar(subtype) != is_scalar(object_subtype) {
+            diagnostics.error(
+                pos,
+                format!(
+                    "Alias subtype does not match aliased object '{}'",
+                    aliased.designator()
+                ),
+            );
+        }
+    }
+}
+
+/// Check that an aggregate with only null range choices is not the value of an object
+/// whose index constraint is not null, only integer literal bounds are considered
+fn check_null_aggregate(
+    subtype_indication: &SubtypeIndication,
+    expr: &WithPos<Expression>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let index_range = match subtype_indication.constraint {
+        Some(WithPos {
+            item: SubtypeConstraint::Array(ref ranges, _),
+            ..
+        }) if ranges.len() == 1 => match ranges[0] {
+            DiscreteRange::Range(ast::Range::Range(ref constraint)) => constraint,
+            _ => return,
+        },
+        _ => return,
+    };
+
+    if is_static_null_range(index_range) != Some(false) {
+        return;
+    }
+
+    let assocs = match expr.item {
+        Expression::Aggregate(ref assocs) if !assocs.is_empty() => assocs,
+        _ => return,
+    };
+
+    let is_null = assocs.iter().all(|assoc| match assoc {
+        ElementAssociation::Named(ref choices, _) => choices.iter().all(|choice| match choice {
+            Choice::DiscreteRange(DiscreteRange::Range(ast::Range::Range(ref constraint))) => {
+                is_static_null_range(constraint) == Some(true)
+            }
+            _ => false,
+        }),
+        ElementAssociation::Positional(..) => false,
+    });
+
+    if is_null {
+        diagnostics.error(
+            expr,
+            "Aggregate produces a null array but target is non-null",
+        );
+    }
+}
+
+/// The named entity of an attribute specification must be of the specified entity class
+/// Only entities which are declared in the same declarative part are checked
+fn check_attribute_entity_class(
+    region: &Region<'_>,
+    attr_spec: &AttributeSpecification,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let designator = if let EntityName::Name(ref tag) = attr_spec.entity_name {
+        &tag.designator
+    } else {
+        return;
+    };
+
+    let visible = if let Some(visible) = region.lookup_immediate(&designator.item) {
+        visible
+    } else {
+        return;
+    };
+
+    let is_of_class = |ent: &NamedEntity| -> Option<bool> {
+        let is_object_of = |class: ObjectClass| match ent.actual_kind() {
+            NamedEntityKind::Object(ref object) => object.class == class,
+            NamedEntityKind::InterfaceObject(ref object) => object.class == class,
+            NamedEntityKind::DeferredConstant => class == ObjectClass::Constant,
+            _ => false,
+        };
+
+        let is_subtype = match ent.actual_kind() {
+            NamedEntityKind::Subtype(..) => true,
+            _ => false,
+        };
+
+        let is_of_class = match attr_spec.entity_class {
+            EntityClass::Signal => is_object_of(ObjectClass::Signal),
+            EntityClass::Variable => {
+                is_object_of(ObjectClass::Variable) || is_object_of(ObjectClass::SharedVariable)
+            }
+            EntityClass::Constant => is_object_of(ObjectClass::Constant),
+            EntityClass::Type => ent.actual_kind().is_type() && !is_subtype,
+            EntityClass::Subtype => is_subtype,
+            EntityClass::Component => match ent.actual_kind() {
+                NamedEntityKind::Component => true,
+                _ => false,
+            },
+            EntityClass::File => match ent.actual_kind() {
+                NamedEntityKind::File | NamedEntityKind::InterfaceFile(..) => true,
+                _ => false,
+            },
+            EntityClass::Group => match ent.actual_kind() {
+                NamedEntityKind::Group => true,
+                _ => false,
+            },
+            EntityClass::Units => match ent.actual_kind() {
+                NamedEntityKind::PhysicalLiteral => true,
+                _ => false,
+            },
+            EntityClass::Procedure | EntityClass::Function => {
+                let is_function = attr_spec.entity_class == EntityClass::Function;
+                match ent.actual_kind() {
+                    NamedEntityKind::Subprogram(ref signature)
+                    | NamedEntityKind::SubprogramDecl(ref signature) => {
+                        signature.is_function() == is_function
+                    }
+                    _ => false,
+                }
+            }
+            // @TODO design units and labels are not yet distinguished by the region
+            EntityClass::Entity
+            | EntityClass::Architecture
+            | EntityClass::Configuration
+            | EntityClass::Package
+            | EntityClass::Label => return None,
+        };
+        Some(is_of_class)
+    };
+
+    let is_of_class = match visible {
+        NamedEntities::Single(ent) => is_of_class(ent),
+        NamedEntities::Overloaded(overloaded) => {
+            let mut classes = overloaded.entities().map(|ent| is_of_class(ent));
+            Some(classes.any(|class| class == Some(true)))
+        }
+    };
+
+    if is_of_class == Some(false) {
+        diagnostics.error(
+            designator,
+            format!(
+                "Entity '{}' is not of class '{}'",
+                designator.item, attr_spec.entity_class
+            ),
+        );
+    }
+}
+
+/// A range type definition is a floating point type when a bound is a real literal
+fn is_floating_range(range: &ast::Range) -> bool {
+    fn is_real(expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Real(..))) => true,
+            Expression::Unary(_, ref operand) => is_real(&operand.item),
+            _ => false,
+        }
+    }
+
+    if let ast::Range::Range(ref constraint) = range {
+        is_real(&constraint.left_expr.item) || is_real(&constraint.right_expr.item)
+    } else {
+        false
+    }
+}
@@ -5,9 +5,11 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::ast::search::*;
 use crate::ast::*;
 use crate::data::*;
 use analyze::*;
+use concurrent::check_associations;
 use region::*;
 use root::*;
 use semantic::{uninstantiated_package_prefix_error, ResolvedName};
@@ -21,6 +23,17 @@ impl<'a> AnalyzeContext<'a> {
         root_region: &mut Region<'_>,
         region: &mut Region<'_>,
         diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalNullResult {
+        self.analyze_design_unit_kind(id, unit, root_region, region, diagnostics)
+    }
+
+    fn analyze_design_unit_kind(
+        &self,
+        id: EntityId,
+        unit: &mut AnyDesignUnit,
+        root_region: &mut Region<'_>,
+        region: &mut Region<'_>,
+        diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         match unit {
             AnyDesignUnit::Primary(unit) => match unit {
@@ -42,9 +55,11 @@ impl<'a> AnalyzeContext<'a> {
             },
             AnyDesignUnit::Secondary(unit) => match unit {
                 AnySecondaryUnit::Architecture(unit) => {
-                    self.analyze_architecture(id, unit, diagnostics)
+                    self.analyze_architecture(id, unit, root_region, diagnostics)
+                }
+                AnySecondaryUnit::PackageBody(unit) => {
+                    self.analyze_package_body(unit, root_region, diagnostics)
                 }
-                AnySecondaryUnit::PackageBody(unit) => self.analyze_package_body(unit, diagnostics),
             },
         }
     }
@@ -75,7 +90,9 @@ impl<'a> AnalyzeContext<'a> {
         );
 
         if let Some(ref mut list) = unit.generic_clause {
-            self.analyze_interface_list(&mut primary_region, list, diagnostics)?;
+            let mut generic_diagnostics = Vec::new();
+            self.analyze_interface_list(&mut primary_region, list, &mut generic_diagnostics)?;
+            report_generics_used_before_declaration(list, generic_diagnostics, diagnostics);
         }
         if let Some(ref mut list) = unit.port_clause {
             self.analyze_interface_list(&mut primary_region, list, diagnostics)?;
@@ -119,6 +136,14 @@ impl<'a> AnalyzeContext<'a> {
                 err.add_to(diagnostics)?;
             }
         };
+
+        for item in unit.decl.iter_mut() {
+            match item {
+                ConfigurationDeclarativeItem::Use(ref mut use_clause) => {
+                    self.analyze_use_clause(&mut root_region, &mut use_clause.item, diagnostics)?;
+                }
+            }
+        }
         Ok(())
     }
 
@@ -175,12 +200,13 @@ impl<'a> AnalyzeContext<'a> {
         match self.analyze_package_instance_name(root_region, &mut unit.package_name) {
             Ok(package_region) => {
                 *region = (*package_region).clone();
-                Ok(())
+                self.check_package_generic_map(root_region, unit, diagnostics)
             }
             Err(AnalysisError::NotFatal(diagnostic)) => {
                 diagnostics.push(diagnostic);
                 Ok(())
             }
+            Err(AnalysisError::Unknown) => Ok(()),
             Err(AnalysisError::Fatal(err)) => Err(err),
         }
     }
@@ -204,6 +230,7 @@ impl<'a> AnalyzeContext<'a> {
         &self,
         id: EntityId,
         unit: &mut ArchitectureBody,
+        unit_root_region: &mut Region<'_>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         unit.entity_name.clear_reference();
@@ -241,12 +268,23 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_declarative_part(&mut region, &mut unit.decl, diagnostics)?;
         self.analyze_concurrent_part(&mut region, &mut unit.statements, diagnostics)?;
         region.close(diagnostics);
+        let unprotected = unprotected_shared_variables(&region, &unit.decl);
+        drop(region);
+
+        // Only keep the visibility added by the context clause of the secondary unit
+        *unit_root_region = root_region.without_parent();
+
+        if self.lints_enabled() {
+            lint::check_unused_components(unit, diagnostics);
+            lint::check_shared_variable_writers(unit, &unprotected, diagnostics);
+        }
         Ok(())
     }
 
     fn analyze_package_body(
         &self,
         unit: &mut PackageBody,
+        unit_root_region: &mut Region<'_>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         unit.ident.clear_reference();
@@ -273,6 +311,9 @@ impl<'a> AnalyzeContext<'a> {
 
         self.analyze_declarative_part(&mut region, &mut unit.decl, diagnostics)?;
         region.close(diagnostics);
+        drop(region);
+
+        *unit_root_region = root_region.without_parent();
         Ok(())
     }
 
@@ -465,6 +506,13 @@ impl<'a> AnalyzeContext<'a> {
         context_clause: &mut [WithPos<ContextItem>],
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
+        check_repeated_context_items(
+            &self.work_sym,
+            self.work_library_name(),
+            context_clause,
+            diagnostics,
+        );
+
         for context_item in context_clause.iter_mut() {
             match context_item.item {
                 ContextItem::Library(LibraryClause { ref name_list }) => {
@@ -476,6 +524,14 @@ impl<'a> AnalyzeContext<'a> {
                             ))
                         } else if let Some(library) = self.get_library(&library_name.item) {
                             region.make_potentially_visible(Some(&library_name.pos), library);
+                        } else if self.resolution_strictness() == ResolutionStrictness::Lenient {
+                            // Assume the library exists but was not loaded
+                            let library = Arc::new(NamedEntity::new(
+                                Designator::Identifier(library_name.item.clone()),
+                                NamedEntityKind::Library,
+                                None,
+                            ));
+                            region.make_potentially_visible(Some(&library_name.pos), library);
                         } else {
                             diagnostics.push(Diagnostic::error(
                                 &library_name,
@@ -613,6 +669,30 @@ impl<'a> AnalyzeContext<'a> {
             ))
         }
     }
+
+    /// Check the generic map of a package instantiated as 'lib.pkg' against its generics
+    pub fn check_package_generic_map(
+        &self,
+        region: &Region<'_>,
+        instance: &PackageInstantiation,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalNullResult {
+        let package_name = &instance.package_name;
+        if let Some(data) = self.lookup_library_unit(region, package_name, PrimaryKind::Package)? {
+            if let AnyDesignUnit::Primary(AnyPrimaryUnit::Package(ref package)) = *data {
+                let generics = package.generic_clause.as_deref().unwrap_or(&[]);
+                check_associations(
+                    &format!("package '{}'", package.ident.item),
+                    "generic",
+                    generics,
+                    instance.generic_map.as_deref().unwrap_or(&[]),
+                    &package_name.pos,
+                    diagnostics,
+                );
+            }
+        }
+        Ok(())
+    }
 }
 
 pub enum UsedNames {
@@ -622,3 +702,152 @@ pub enum UsedNames {
     /// @TODO add pos for where declaration was made visible into VisibleDeclaration
     AllWithin(SrcPos, Arc<NamedEntity>),
 }
+
+/// The declaration positions of shared variables whose type is not a protected type
+fn unprotected_shared_variables(region: &Region<'_>, decls: &[Declaration]) -> Vec<SrcPos> {
+    let mut unprotected = Vec::new();
+    for decl in decls.iter() {
+        if let Declaration::Object(ObjectDeclaration {
+            class: ObjectClass::SharedVariable,
+            ref ident,
+            ..
+        }) = decl
+        {
+            let is_protected = region
+                .lookup_within(&ident.pos, &Designator::Identifier(ident.item.clone()))
+                .ok()
+                .and_then(|visible| visible.into_non_overloaded().ok())
+                .map(|ent| match ent.actual_kind() {
+                    NamedEntityKind::Object(Object {
+                        subtype: Some(ref subtype),
+                        ..
+                    }) => subtype.base_type().kind().is_protected_type(),
+                    _ => false,
+                });
+
+            // Unknown types are not reported
+            if is_protected == Some(false) {
+                unprotected.push(ident.pos.clone());
+            }
+        }
+    }
+    unprotected
+}
+
+/// Repeating a library clause or context reference within a context clause is legal but redundant
+/// Context references by the work library and by its name refer to the same context
+fn check_repeated_context_items(
+    work_sym: &Symbol,
+    work_library_name: &Symbol,
+    items: &[WithPos<ContextItem>],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut libraries: Vec<&Ident> = Vec::new();
+    let mut contexts: Vec<(String, &SrcPos)> = Vec::new();
+
+    for context_item in items.iter() {
+        match context_item.item {
+            ContextItem::Library(LibraryClause { ref name_list }) => {
+                for library_name in name_list.iter() {
+                    if let Some(prev) = libraries.iter().find(|prev| prev.item == library_name.item)
+                    {
+                        diagnostics.push(
+                            Diagnostic::hint(
+                                library_name,
+                                format!("Duplicate library clause of '{}'", library_name.item),
+                            )
+                            .related(prev, "Previously made visible here"),
+                        );
+                    } else {
+                        libraries.push(library_name);
+                    }
+                }
+            }
+            ContextItem::Context(ContextReference { ref name_list }) => {
+                for name in name_list.iter() {
+                    let text = name.item.to_string();
+                    let key = match name.item {
+                        Name::Selected(ref prefix, ref suffix) => match prefix.item {
+                            Name::Designator(ref designator)
+                                if designator.item == Designator::Identifier(work_sym.clone()) =>
+                            {
+                                format!("{}.{}", work_library_name, suffix)
+                            }
+                            _ => text.clone(),
+                        },
+                        _ => text.clone(),
+                    };
+                    if let Some((_, prev_pos)) = contexts.iter().find(|(prev, _)| prev == &key) {
+                        diagnostics.push(
+                            Diagnostic::hint(
+                                name,
+                                format!("Duplicate context reference of '{}'", text),
+                            )
+                            .related(prev_pos, "Previously referenced here"),
+                        );
+                    } else {
+                        contexts.push((key, &name.pos));
+                    }
+                }
+            }
+            ContextItem::Use(..) => {}
+        }
+    }
+}
+
+/// A generic is only visible to the generics declared after it
+/// Replace the missing declaration errors of generics referenced too early
+fn report_generics_used_before_declaration(
+    generics: &[InterfaceDeclaration],
+    generic_diagnostics: Vec<Diagnostic>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut early_uses = Vec::new();
+
+    for (idx, generic) in generics.iter().enumerate() {
+        let mut searcher = UnresolvedNames::default();
+        let _ = generic.search(&mut searcher);
+
+        for (pos, designator) in searcher.names {
+            let is_later_generic = generics[idx + 1..].iter().any(|later| match later {
+                InterfaceDeclaration::Object(ref object) => {
+                    designator == Designator::Identifier(object.ident.item.clone())
+                }
+                InterfaceDeclaration::Type(ref typ) => {
+                    designator == Designator::Identifier(typ.ident.item.clone())
+                }
+                _ => false,
+            });
+            if is_later_generic {
+                early_uses.push((pos, designator));
+            }
+        }
+    }
+
+    for diagnostic in generic_diagnostics {
+        if let Some((pos, designator)) = early_uses.iter().find(|(pos, _)| pos == &diagnostic.pos) {
+            diagnostics.error(
+                pos,
+                format!("Generic '{}' used before declaration", designator),
+            );
+        } else {
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+#[derive(Default)]
+struct UnresolvedNames {
+    names: Vec<(SrcPos, Designator)>,
+}
+
+impl Searcher for UnresolvedNames {
+    fn search_name(&mut self, pos: &SrcPos, name: &Name) -> SearchState {
+        if let Name::Designator(ref designator) = name {
+            if designator.reference.is_none() {
+                self.names.push((pos.clone(), designator.item.clone()));
+            }
+        }
+        NotFinished
+    }
+}
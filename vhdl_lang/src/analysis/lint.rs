@@ -0,0 +1,816 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! Opt-in checks which are not required by the LRM
+//! They run on the AST after it has been analyzed and all references are set
+
+use super::semantic::static_integer;
+use crate::ast;
+use crate::ast::search::*;
+use crate::ast::*;
+use crate::data::*;
+
+/// Warn about components declared in the architecture which are never instantiated
+pub fn check_unused_components(
+    architecture: &ArchitectureBody,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let instantiated: Vec<&SrcPos> = concurrent_statements(&architecture.statements)
+        .into_iter()
+        .filter_map(|statement| match statement {
+            ConcurrentStatement::Instance(ref instance) => match instance.unit {
+                InstantiatedUnit::Component(ref name) => name.item.suffix_reference(),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+
+    for decl in architecture.decl.iter() {
+        if let Declaration::Component(ref component) = decl {
+            if !instantiated.contains(&&component.ident.pos) {
+                diagnostics.warning(
+                    &component.ident,
+                    format!(
+                        "Component '{}' is declared but never instantiated",
+                        component.ident.item
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Warn about protected types which declare no subprograms
+pub fn check_empty_protected_types(unit: &AnyDesignUnit, diagnostics: &mut dyn DiagnosticHandler) {
+    for decl in unit.declarative_regions().into_iter().flatten() {
+        if let Declaration::Type(TypeDeclaration {
+            ref ident,
+            def: TypeDefinition::Protected(ref protected),
+        }) = decl
+        {
+            if protected.items.is_empty() {
+                diagnostics.warning(
+                    ident,
+                    format!("Protected type '{}' declares no methods", ident.item),
+                );
+            }
+        }
+    }
+}
+
+/// Hint about parameters with the same name as their subprogram,
+/// the parameter hides the subprogram such that it cannot be called recursively
+pub fn check_parameter_shadowing(unit: &AnyDesignUnit, diagnostics: &mut dyn DiagnosticHandler) {
+    for decl in unit.declarative_regions().into_iter().flatten() {
+        let specification = match decl {
+            Declaration::SubprogramDeclaration(ref specification) => specification,
+            Declaration::SubprogramBody(ref body) => &body.specification,
+            _ => continue,
+        };
+
+        let (designator, parameter_list) = match specification {
+            SubprogramDeclaration::Procedure(ref procedure) => {
+                (&procedure.designator.item, &procedure.parameter_list)
+            }
+            SubprogramDeclaration::Function(ref function) => {
+                (&function.designator.item, &function.parameter_list)
+            }
+        };
+
+        let name = match designator {
+            SubprogramDesignator::Identifier(ref name) => name,
+            SubprogramDesignator::OperatorSymbol(..) => continue,
+        };
+
+        for parameter in parameter_list.iter() {
+            let ident = match parameter {
+                InterfaceDeclaration::Object(ref object) => &object.ident,
+                InterfaceDeclaration::File(ref file) => &file.ident,
+                _ => continue,
+            };
+
+            if &ident.item == name {
+                diagnostics.push(Diagnostic::hint(
+                    ident,
+                    format!(
+                        "Parameter '{}' shadows enclosing subprogram name",
+                        ident.item
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Hint about if branches which only contain a null statement
+pub fn check_null_only_branches(unit: &AnyDesignUnit, diagnostics: &mut dyn DiagnosticHandler) {
+    for part in collect_sequential_parts(unit) {
+        for statement in sequential_statements(part) {
+            if let SequentialStatement::If(ref ifstmt) = statement {
+                for conditional in ifstmt.conditionals.iter() {
+                    if is_null_only(&conditional.item) {
+                        diagnostics.push(Diagnostic::hint(
+                            &conditional.condition,
+                            "Empty branch contains only 'null'",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The statements of subprogram bodies and processes within the design unit
+fn collect_sequential_parts(unit: &AnyDesignUnit) -> Vec<&[LabeledSequentialStatement]> {
+    let mut parts = Vec::new();
+    for decl in unit.declarative_regions().into_iter().flatten() {
+        if let Declaration::SubprogramBody(ref body) = decl {
+            parts.push(body.statements.as_slice());
+        }
+    }
+
+    let statements = match unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) => &entity.statements,
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref architecture)) => {
+            &architecture.statements
+        }
+        _ => return parts,
+    };
+
+    for process in processes(statements) {
+        parts.push(process.statements.as_slice());
+    }
+    parts
+}
+
+fn is_null_only(statements: &[LabeledSequentialStatement]) -> bool {
+    statements.len() == 1 && statements[0].statement == SequentialStatement::Null
+}
+
+/// Warn about while loops whose condition is statically true or false
+pub fn check_static_while_loops(unit: &AnyDesignUnit, diagnostics: &mut dyn DiagnosticHandler) {
+    for part in collect_sequential_parts(unit) {
+        for statement in sequential_statements(part) {
+            if let SequentialStatement::Loop(LoopStatement {
+                iteration_scheme: Some(IterationScheme::While(ref condition)),
+                ref statements,
+            }) = statement
+            {
+                match static_boolean(&condition.item) {
+                    Some(false) => diagnostics.warning(
+                        condition,
+                        "Loop condition is always false; body never executes",
+                    ),
+                    Some(true) if !can_leave_loop(statements, false) => {
+                        diagnostics.warning(condition, "Loop never terminates")
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Folds conditions built from the boolean literals and logical operators
+fn static_boolean(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::Name(ref name) => {
+            if let Name::Designator(ref designator) = **name {
+                if let Designator::Identifier(ref sym) = designator.item {
+                    if identifiers_equal(&sym.name_utf8(), "true") {
+                        return Some(true);
+                    } else if identifiers_equal(&sym.name_utf8(), "false") {
+                        return Some(false);
+                    }
+                }
+            }
+            None
+        }
+        Expression::Unary(Unary::Not, ref operand) => static_boolean(&operand.item).map(|b| !b),
+        Expression::Binary(op, ref left, ref right) => {
+            let left = static_boolean(&left.item)?;
+            let right = static_boolean(&right.item)?;
+            match op {
+                Binary::And => Some(left && right),
+                Binary::Or => Some(left || right),
+                Binary::Nand => Some(!(left && right)),
+                Binary::Nor => Some(!(left || right)),
+                Binary::Xor => Some(left != right),
+                Binary::Xnor | Binary::EQ => Some(left == right),
+                Binary::NE => Some(left != right),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// True if the statements contain an exit or return which may leave the loop
+/// An unlabeled exit within a nested loop only leaves the nested loop
+fn can_leave_loop(statements: &[LabeledSequentialStatement], nested: bool) -> bool {
+    statements
+        .iter()
+        .any(|statement| match statement.statement {
+            SequentialStatement::Exit(ref exit) => !nested || exit.loop_label.is_some(),
+            SequentialStatement::Return(..) => true,
+            SequentialStatement::If(ref ifstmt) => {
+                ifstmt
+                    .conditionals
+                    .iter()
+                    .any(|conditional| can_leave_loop(&conditional.item, nested))
+                    || ifstmt
+                        .else_item
+                        .as_ref()
+                        .map(|else_item| can_leave_loop(else_item, nested))
+                        .unwrap_or(false)
+            }
+            SequentialStatement::Case(ref case_stmt) => case_stmt
+                .alternatives
+                .iter()
+                .any(|alternative| can_leave_loop(&alternative.item, nested)),
+            SequentialStatement::Loop(ref loop_stmt) => can_leave_loop(&loop_stmt.statements, true),
+            _ => false,
+        })
+}
+
+/// Integer types with more values than this are not checked for full coverage by choices
+const MAX_CHECKED_VALUES: i64 = 256;
+
+/// Warn about case statements without others where the choices cannot be proven to cover
+/// all values of the case expression type, only types declared within the unit or within
+/// the declarations of std.standard are checked
+pub fn check_case_coverage(
+    unit: &AnyDesignUnit,
+    standard: &[Declaration],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut regions = unit.declarative_regions();
+    regions.push(standard);
+
+    for part in collect_sequential_parts(unit) {
+        for statement in sequential_statements(part) {
+            if let SequentialStatement::Case(ref case_stmt) = statement {
+                let has_others = case_stmt
+                    .alternatives
+                    .iter()
+                    .any(|alternative| alternative.choices.contains(&Choice::Others));
+                if has_others {
+                    continue;
+                }
+
+                // Nothing is known about the values of types declared in other packages
+                let values = match case_values(&case_stmt.expression.item, &regions) {
+                    Some(values) => values,
+                    None => continue,
+                };
+
+                if !values.is_covered_by(&case_stmt.alternatives) {
+                    // Insert a new alternative before 'end case' indented one level deeper
+                    let end_pos = &case_stmt.end_pos;
+                    let indent = " ".repeat(end_pos.start().character as usize);
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            &case_stmt.expression,
+                            "Case may not cover all values; consider 'others'",
+                        )
+                        .with_fixit(
+                            end_pos.source.pos(end_pos.start(), end_pos.start()),
+                            format!("  when others => null;\n{}", indent),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The values of a discrete type whose declaration is found within the design unit or std.standard
+enum DiscreteValues<'a> {
+    Enumeration(&'a [WithPos<EnumerationLiteral>]),
+    Integer(i64, i64),
+}
+
+impl<'a> DiscreteValues<'a> {
+    /// The position of the static value of the choice expression among the values
+    fn index_of(&self, expr: &Expression) -> Option<i64> {
+        match self {
+            DiscreteValues::Enumeration(literals) => {
+                let designator = match expr {
+                    Expression::Name(ref name) => match **name {
+                        Name::Designator(ref designator) => &designator.item,
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+
+                literals
+                    .iter()
+                    .position(|literal| match (&literal.item, designator) {
+                        (
+                            EnumerationLiteral::Identifier(ref lit),
+                            Designator::Identifier(ref sym),
+                        ) => lit == sym,
+                        (EnumerationLiteral::Character(lit), Designator::Character(chr)) => {
+                            lit == chr
+                        }
+                        _ => false,
+                    })
+                    .map(|idx| idx as i64)
+            }
+            DiscreteValues::Integer(low, _) => {
+                static_integer(expr).and_then(|value| value.checked_sub(*low))
+            }
+        }
+    }
+
+    /// The number of values or None when there are too many to be counted
+    fn len(&self) -> Option<i64> {
+        match self {
+            DiscreteValues::Enumeration(literals) => Some(literals.len() as i64),
+            DiscreteValues::Integer(low, high) => high
+                .checked_sub(*low)
+                .and_then(|diff| diff.checked_add(1))
+                .map(|len| len.max(0)),
+        }
+    }
+
+    fn is_covered_by<T>(&self, alternatives: &[Alternative<T>]) -> bool {
+        let len = match self.len() {
+            Some(len) if len <= MAX_CHECKED_VALUES => len,
+            _ => return false,
+        };
+
+        let mut covered = vec![false; len as usize];
+        for choice in alternatives
+            .iter()
+            .flat_map(|alternative| alternative.choices.iter())
+        {
+            let (left, right) = match choice {
+                Choice::Expression(ref expr) => {
+                    let index = self.index_of(&expr.item);
+                    (index, index)
+                }
+                Choice::DiscreteRange(DiscreteRange::Range(ast::Range::Range(ref constraint))) => {
+                    let left = self.index_of(&constraint.left_expr.item);
+                    let right = self.index_of(&constraint.right_expr.item);
+                    match constraint.direction {
+                        Direction::Ascending => (left, right),
+                        Direction::Descending => (right, left),
+                    }
+                }
+                _ => (None, None),
+            };
+
+            if let (Some(left), Some(right)) = (left, right) {
+                for index in left.max(0)..=right.min(len - 1) {
+                    covered[index as usize] = true;
+                }
+            }
+        }
+
+        covered.into_iter().all(|covered| covered)
+    }
+}
+
+/// The values of the case expression when it is the name of an object declared within the unit
+fn case_values<'a>(expr: &Expression, regions: &[&'a [Declaration]]) -> Option<DiscreteValues<'a>> {
+    let reference = match expr {
+        Expression::Name(ref name) => match **name {
+            Name::Designator(ref designator) => designator.reference.as_ref()?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let subtype_indication =
+        regions
+            .iter()
+            .flat_map(|decl| decl.iter())
+            .find_map(|decl| match decl {
+                Declaration::Object(ref object) if &object.ident.pos == reference => {
+                    Some(&object.subtype_indication)
+                }
+                _ => None,
+            })?;
+
+    subtype_values(subtype_indication, regions)
+}
+
+fn subtype_values<'a>(
+    subtype_indication: &'a SubtypeIndication,
+    regions: &[&'a [Declaration]],
+) -> Option<DiscreteValues<'a>> {
+    if let Some(ref constraint) = subtype_indication.constraint {
+        if let SubtypeConstraint::Range(ast::Range::Range(ref constraint)) = constraint.item {
+            // A range which is not static is within the values of the type mark
+            if let Some(values) = integer_values(constraint) {
+                return Some(values);
+            }
+        }
+    }
+
+    let reference = match subtype_indication.type_mark.item {
+        SelectedName::Designator(ref designator) => designator.reference.as_ref()?,
+        SelectedName::Selected(..) => return None,
+    };
+
+    let def = regions
+        .iter()
+        .flat_map(|decl| decl.iter())
+        .find_map(|decl| match decl {
+            Declaration::Type(ref type_decl) if &type_decl.ident.pos == reference => {
+                Some(&type_decl.def)
+            }
+            _ => None,
+        })?;
+
+    match def {
+        TypeDefinition::Enumeration(ref literals) => Some(DiscreteValues::Enumeration(literals)),
+        TypeDefinition::Integer(ast::Range::Range(ref constraint)) => integer_values(constraint),
+        TypeDefinition::Subtype(ref subtype_indication) => {
+            subtype_values(subtype_indication, regions)
+        }
+        _ => None,
+    }
+}
+
+fn integer_values<'a>(constraint: &RangeConstraint) -> Option<DiscreteValues<'a>> {
+    let left = static_integer(&constraint.left_expr.item)?;
+    let right = static_integer(&constraint.right_expr.item)?;
+    match constraint.direction {
+        Direction::Ascending => Some(DiscreteValues::Integer(left, right)),
+        Direction::Descending => Some(DiscreteValues::Integer(right, left)),
+    }
+}
+
+/// Warn about shared variables of non-protected types which are written by several processes
+pub fn check_shared_variable_writers(
+    architecture: &ArchitectureBody,
+    unprotected: &[SrcPos],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let mut writers: Vec<Vec<SrcPos>> = Vec::new();
+    for process in processes(&architecture.statements) {
+        let mut written = Drivers::default();
+        for statement in sequential_statements(&process.statements) {
+            if let SequentialStatement::VariableAssignment(ref assign) = statement {
+                written.add(&assign.target);
+            }
+        }
+        writers.push(written.references);
+    }
+
+    for decl in architecture.decl.iter() {
+        if let Declaration::Object(ref object) = decl {
+            if !unprotected.contains(&object.ident.pos) {
+                continue;
+            }
+
+            let num_writers = writers
+                .iter()
+                .filter(|written| written.contains(&object.ident.pos))
+                .count();
+
+            if num_writers > 1 {
+                diagnostics.warning(
+                    &object.ident,
+                    format!(
+                        "Shared variable '{}' accessed by multiple processes",
+                        object.ident.item
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Flattens nested sequential statements such as those within if and loop statements
+fn sequential_statements(statements: &[LabeledSequentialStatement]) -> Vec<&SequentialStatement> {
+    let mut collected = Vec::new();
+    collect_sequential_statements(statements, &mut collected);
+    collected
+}
+
+fn collect_sequential_statements<'a>(
+    statements: &'a [LabeledSequentialStatement],
+    collected: &mut Vec<&'a SequentialStatement>,
+) {
+    for statement in statements.iter() {
+        collected.push(&statement.statement);
+        match statement.statement {
+            SequentialStatement::If(ref ifstmt) => {
+                for conditional in ifstmt.conditionals.iter() {
+                    collect_sequential_statements(&conditional.item, collected);
+                }
+                if let Some(ref else_item) = ifstmt.else_item {
+                    collect_sequential_statements(else_item, collected);
+                }
+            }
+            SequentialStatement::Case(ref case_stmt) => {
+                for alternative in case_stmt.alternatives.iter() {
+                    collect_sequential_statements(&alternative.item, collected);
+                }
+            }
+            SequentialStatement::Loop(ref loop_stmt) => {
+                collect_sequential_statements(&loop_stmt.statements, collected);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The processes among the flattened concurrent statements
+fn processes(statements: &[LabeledConcurrentStatement]) -> Vec<&ProcessStatement> {
+    concurrent_statements(statements)
+        .into_iter()
+        .filter_map(|statement| match statement {
+            ConcurrentStatement::Process(ref process) => Some(process),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Flattens nested concurrent statements such as those within blocks and generate statements
+fn concurrent_statements(statements: &[LabeledConcurrentStatement]) -> Vec<&ConcurrentStatement> {
+    let mut collected = Vec::new();
+    collect_concurrent_statements(statements, &mut collected);
+    collected
+}
+
+fn collect_concurrent_statements<'a>(
+    statements: &'a [LabeledConcurrentStatement],
+    collected: &mut Vec<&'a ConcurrentStatement>,
+) {
+    for statement in statements.iter() {
+        collected.push(&statement.statement);
+        match statement.statement {
+            ConcurrentStatement::Block(ref block) => {
+                collect_concurrent_statements(&block.statements, collected);
+            }
+            ConcurrentStatement::ForGenerate(ref gen) => {
+                collect_concurrent_statements(&gen.body.statements, collected);
+            }
+            ConcurrentStatement::IfGenerate(ref gen) => {
+                for conditional in gen.conditionals.iter() {
+                    collect_concurrent_statements(&conditional.item.statements, collected);
+                }
+                if let Some(ref else_item) = gen.else_item {
+                    collect_concurrent_statements(&else_item.statements, collected);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref gen) => {
+                for alternative in gen.alternatives.iter() {
+                    collect_concurrent_statements(&alternative.item.statements, collected);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Warn about concurrent calls of procedures declared within the design unit
+/// which wait for a static condition, the equivalent process may never continue
+pub fn check_concurrent_procedure_calls(
+    unit: &AnyDesignUnit,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let statements = match unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) => &entity.statements,
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref architecture)) => {
+            &architecture.statements
+        }
+        _ => return,
+    };
+
+    let regions = unit.declarative_regions();
+
+    for statement in concurrent_statements(statements) {
+        let call = match statement {
+            ConcurrentStatement::ProcedureCall(ref pcall) => &pcall.call,
+            _ => continue,
+        };
+
+        let body = match call.name.item {
+            Name::Designator(ref designator) => find_procedure_body(&designator.item, &regions),
+            _ => None,
+        };
+        let body = match body {
+            Some(body) => body,
+            None => continue,
+        };
+
+        for statement in sequential_statements(&body.statements) {
+            if let SequentialStatement::Wait(WaitStatement {
+                ref sensitivity_clause,
+                condition_clause: Some(ref condition),
+                timeout_clause: None,
+            }) = statement
+            {
+                if sensitivity_clause.is_empty() && static_boolean(&condition.item).is_some() {
+                    diagnostics.push(
+                        Diagnostic::warning(
+                            &call.name,
+                            format!(
+                                "Concurrent call to procedure '{}' may loop forever",
+                                body.specification.designator().item
+                            ),
+                        )
+                        .related(condition, "Wait with static condition"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The body of a procedure with the designator, there are no references to overloaded
+/// subprograms so the procedure is found by name within the design unit
+fn find_procedure_body<'a>(
+    designator: &Designator,
+    regions: &[&'a [Declaration]],
+) -> Option<&'a SubprogramBody> {
+    regions
+        .iter()
+        .flat_map(|decl| decl.iter())
+        .find_map(|decl| match decl {
+            Declaration::SubprogramBody(
+                ref body @ SubprogramBody {
+                    specification: SubprogramDeclaration::Procedure(..),
+                    ..
+                },
+            ) if &body.specification.designator().item == designator => Some(body),
+            _ => None,
+        })
+}
+
+/// Hint about subprograms which call themselves before any if, case or loop statement,
+/// without a terminating condition the recursion is unbounded
+pub fn check_unbounded_recursion(unit: &AnyDesignUnit, diagnostics: &mut dyn DiagnosticHandler) {
+    for decl in unit.declarative_regions().into_iter().flatten() {
+        let body = match decl {
+            Declaration::SubprogramBody(ref body) => body,
+            _ => continue,
+        };
+
+        let designator = body.specification.designator();
+        if let Designator::OperatorSymbol(..) = designator.item {
+            continue;
+        }
+
+        let mut self_calls = SelfCalls {
+            designator: &designator,
+            calls: Vec::new(),
+        };
+
+        for statement in body.statements.iter() {
+            match statement.statement {
+                // The statement may contain the terminating condition of later calls
+                SequentialStatement::If(..)
+                | SequentialStatement::Case(..)
+                | SequentialStatement::Loop(..) => break,
+                _ => {
+                    let _ = statement.search(&mut self_calls);
+                }
+            }
+        }
+
+        for pos in self_calls.calls {
+            diagnostics.hint(
+                pos,
+                format!(
+                    "Subprogram '{}' calls itself without a terminating condition",
+                    designator.item
+                ),
+            );
+        }
+    }
+}
+
+/// Calls of a subprogram found by name since there are no references to overloaded subprograms
+/// Names referencing another declaration such as a parameter with the same name are ignored
+struct SelfCalls<'a> {
+    designator: &'a WithPos<Designator>,
+    calls: Vec<SrcPos>,
+}
+
+impl<'a> Searcher for SelfCalls<'a> {
+    fn search_name(&mut self, pos: &SrcPos, name: &Name) -> SearchState {
+        if let Name::Designator(ref designator) = name {
+            let is_subprogram = match designator.reference {
+                Some(ref decl_pos) => decl_pos == &self.designator.pos,
+                None => true,
+            };
+            if designator.item == self.designator.item && is_subprogram {
+                self.calls.push(pos.clone());
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Warn about an entity which has no architecture within its library
+pub fn check_missing_architecture(
+    entity: &EntityDeclaration,
+    architectures: &[&ArchitectureBody],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if architectures.is_empty() {
+        diagnostics.warning(
+            &entity.ident,
+            format!("Entity '{}' has no architecture", entity.ident.item),
+        );
+    }
+}
+
+/// Warn about output ports which are not driven by any architecture of the entity
+/// Association with a port of an instance or a procedure call is considered to drive it
+pub fn check_undriven_outputs(
+    entity: &EntityDeclaration,
+    architectures: &[&ArchitectureBody],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if architectures.is_empty() {
+        return;
+    }
+
+    let mut drivers = Drivers::default();
+    for architecture in architectures.iter() {
+        collect_drivers(&architecture.statements, &mut drivers);
+    }
+
+    for port in entity.port_clause.iter().flatten() {
+        if let InterfaceDeclaration::Object(ref object) = port {
+            if object.mode == Mode::Out && !drivers.references.contains(&object.ident.pos) {
+                diagnostics.warning(
+                    &object.ident,
+                    format!(
+                        "Output port '{}' is never driven in any architecture",
+                        object.ident.item
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Collects the references of assignment targets and associations
+#[derive(Default)]
+struct Drivers {
+    references: Vec<SrcPos>,
+}
+
+impl Drivers {
+    fn add(&mut self, searchable: &impl Search) {
+        let _ = searchable.search(self);
+    }
+}
+
+impl Searcher for Drivers {
+    fn search_pos_with_ref(&mut self, _pos: &SrcPos, reference: &Reference) -> SearchState {
+        if let Some(ref reference) = reference {
+            self.references.push(reference.clone());
+        }
+        NotFinished
+    }
+}
+
+fn collect_drivers(statements: &[LabeledConcurrentStatement], drivers: &mut Drivers) {
+    for statement in concurrent_statements(statements) {
+        match statement {
+            ConcurrentStatement::Assignment(ref assign) => {
+                drivers.add(&assign.target);
+            }
+            ConcurrentStatement::Instance(ref instance) => {
+                drivers.add(&instance.port_map);
+            }
+            ConcurrentStatement::ProcedureCall(ref pcall) => {
+                drivers.add(&pcall.call);
+            }
+            ConcurrentStatement::Process(ref process) => {
+                collect_sequential_drivers(&process.statements, drivers);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_sequential_drivers(statements: &[LabeledSequentialStatement], drivers: &mut Drivers) {
+    for statement in sequential_statements(statements) {
+        match statement {
+            SequentialStatement::SignalAssignment(ref assign) => {
+                drivers.add(&assign.target);
+            }
+            SequentialStatement::SignalForceAssignment(ref assign) => {
+                drivers.add(&assign.target);
+            }
+            SequentialStatement::ProcedureCall(ref pcall) => {
+                drivers.add(pcall);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! Opt-in timing of the analyzer phases for performance debugging
+
+use crate::ast::UnitId;
+use crate::data::*;
+use std::time::{Duration, Instant};
+
+/// Time spent parsing a source file
+#[derive(Clone, Debug)]
+pub struct SourceMetrics {
+    pub source: Source,
+    pub parse: Duration,
+}
+
+/// Time spent in each check phase of a design unit
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnitTiming {
+    /// Semantic analysis, includes the analysis of dependencies which were not yet analyzed
+    pub analysis: Duration,
+    /// Opt-in lints of the design unit, zero when lints are disabled
+    pub lint: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct UnitMetrics {
+    pub unit_id: UnitId,
+    pub timing: UnitTiming,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    pub sources: Vec<SourceMetrics>,
+    pub units: Vec<UnitMetrics>,
+}
+
+/// Measure the time taken by `f` when enabled, otherwise only call it
+pub(crate) fn timed<T>(enabled: bool, f: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    if enabled {
+        let start = Instant::now();
+        let value = f();
+        (value, Some(start.elapsed()))
+    } else {
+        (f(), None)
+    }
+}
@@ -18,17 +18,24 @@ pub enum NamedEntityKind {
     RecordField,
     Component,
     Attribute,
+    GroupTemplate,
+    Group,
     SubprogramDecl(Signature),
     Subprogram(Signature),
     EnumLiteral(Signature),
     // An optional list of implicit declarations
     // Use Weak reference since implicit declaration typically reference the type itself
     TypeDeclaration(Vec<Weak<NamedEntity>>),
+    // Integer, floating point, physical and enumeration types
+    ScalarType(ScalarClass, Vec<Weak<NamedEntity>>),
+    // True when the index ranges are unbounded with range <>, followed by the implicit declarations
+    ArrayType(bool, Vec<Weak<NamedEntity>>),
+    RecordType,
     Subtype(Subtype),
     IncompleteType,
     InterfaceType,
     Label,
-    Object(ObjectClass),
+    Object(Object),
     InterfaceObject(InterfaceObject),
     PhysicalLiteral,
     DeferredConstant,
@@ -44,12 +51,36 @@ pub enum NamedEntityKind {
     LocalPackageInstance(Arc<Region<'static>>),
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScalarClass {
+    Enumeration,
+    Integer,
+    Floating,
+    Physical,
+}
+
+impl ScalarClass {
+    /// Enumeration and integer types
+    pub fn is_discrete(self) -> bool {
+        match self {
+            ScalarClass::Enumeration | ScalarClass::Integer => true,
+            _ => false,
+        }
+    }
+}
+
 impl NamedEntityKind {
-    pub fn from_object_declaration(decl: &ObjectDeclaration) -> NamedEntityKind {
+    pub fn from_object_declaration(
+        decl: &ObjectDeclaration,
+        subtype: Option<Subtype>,
+    ) -> NamedEntityKind {
         if decl.class == ObjectClass::Constant && decl.expression.is_none() {
             NamedEntityKind::DeferredConstant
         } else {
-            NamedEntityKind::Object(decl.class)
+            NamedEntityKind::Object(Object {
+                class: decl.class,
+                subtype,
+            })
         }
     }
 
@@ -62,7 +93,11 @@ impl NamedEntityKind {
     }
 
     pub fn is_non_deferred_constant(&self) -> bool {
-        if let NamedEntityKind::Object(ObjectClass::Constant) = self {
+        if let NamedEntityKind::Object(Object {
+            class: ObjectClass::Constant,
+            ..
+        }) = self
+        {
             true
         } else {
             false
@@ -83,13 +118,39 @@ impl NamedEntityKind {
             | NamedEntityKind::ProtectedType(..)
             | NamedEntityKind::InterfaceType
             | NamedEntityKind::Subtype(..)
-            | NamedEntityKind::TypeDeclaration(..) => true,
+            | NamedEntityKind::TypeDeclaration(..)
+            | NamedEntityKind::ScalarType(..)
+            | NamedEntityKind::ArrayType(..)
+            | NamedEntityKind::RecordType => true,
             _ => false,
         }
     }
 
+    /// Constants, signals, variables and files
+    pub fn is_object(&self) -> bool {
+        match self {
+            NamedEntityKind::Object(..)
+            | NamedEntityKind::InterfaceObject(..)
+            | NamedEntityKind::DeferredConstant
+            | NamedEntityKind::File
+            | NamedEntityKind::InterfaceFile(..) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_scalar_type(&self) -> bool {
+        if let NamedEntityKind::ScalarType(..) = self {
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn implicit_declarations(&self) -> Vec<Arc<NamedEntity>> {
-        if let NamedEntityKind::TypeDeclaration(ref implicit) = self {
+        if let NamedEntityKind::TypeDeclaration(ref implicit)
+        | NamedEntityKind::ScalarType(_, ref implicit)
+        | NamedEntityKind::ArrayType(_, ref implicit) = self
+        {
             implicit
                 .iter()
                 .map(|ent|
@@ -111,6 +172,8 @@ impl NamedEntityKind {
             RecordField => "file",
             Component => "file",
             Attribute => "file",
+            GroupTemplate => "group template",
+            Group => "group",
             SubprogramDecl(signature) | Subprogram(signature) => {
                 if signature.return_type.is_some() {
                     "function"
@@ -119,12 +182,12 @@ impl NamedEntityKind {
                 }
             }
             EnumLiteral(..) => "enum literal",
-            TypeDeclaration(..) => "type",
+            TypeDeclaration(..) | ScalarType(..) | ArrayType(..) | RecordType => "type",
             Subtype(..) => "subtype",
             IncompleteType => "type",
             InterfaceType => "type",
             Label => "label",
-            Object(class) => class.describe(),
+            Object(object) => object.class.describe(),
             InterfaceObject(object) => object.class.describe(),
             PhysicalLiteral => "physical literal",
             DeferredConstant => "deferred constant",
@@ -147,6 +210,14 @@ impl std::fmt::Debug for NamedEntityKind {
     }
 }
 
+/// Signals, (shared) variables and constants
+#[derive(Clone)]
+pub struct Object {
+    pub class: ObjectClass,
+    /// None when the subtype could not be resolved
+    pub subtype: Option<Subtype>,
+}
+
 /// Signals, (shared) variables and constants
 #[derive(Clone)]
 pub struct InterfaceObject {
@@ -170,6 +241,14 @@ impl Subtype {
     pub fn base(&self) -> &Arc<NamedEntity> {
         &self.base
     }
+
+    /// The type declaration at the end of a chain of subtypes
+    pub fn base_type(&self) -> &Arc<NamedEntity> {
+        match self.base.actual_kind() {
+            NamedEntityKind::Subtype(ref subtype) => subtype.base_type(),
+            _ => &self.base,
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -207,6 +286,23 @@ impl Signature {
         }
     }
 
+    /// True for the signature of a function, false for a procedure
+    pub fn is_function(&self) -> bool {
+        self.return_type.is_some()
+    }
+
+    /// The first pair of parameters at the same position whose names differ
+    pub fn first_renamed_param<'s>(
+        &'s self,
+        other: &'s Signature,
+    ) -> Option<(&'s Arc<NamedEntity>, &'s Arc<NamedEntity>)> {
+        self.params
+            .params
+            .iter()
+            .zip(other.params.params.iter())
+            .find(|(param, other_param)| param.designator() != other_param.designator())
+    }
+
     pub fn key(&self) -> SignatureKey {
         let params = self
             .params
@@ -50,9 +50,14 @@ impl OverloadedName {
             Entry::Occupied(mut entry) => {
                 let old_ent = entry.get();
 
-                if (old_ent.is_implicit() && ent.is_explicit())
-                    || (old_ent.is_subprogram_decl() && ent.is_subprogram())
-                {
+                if old_ent.is_subprogram_decl() && ent.is_subprogram() {
+                    let result = match non_conforming_param(old_ent, &ent) {
+                        Some(diagnostic) => Err(diagnostic),
+                        None => Ok(()),
+                    };
+                    entry.insert(ent);
+                    return result;
+                } else if old_ent.is_implicit() && ent.is_explicit() {
                     entry.insert(ent);
                     return Ok(());
                 } else if old_ent.is_implicit()
@@ -79,7 +84,9 @@ impl OverloadedName {
                     diagnostic.add_related(old_pos, "Previously defined here");
                 }
 
-                Err(diagnostic)
+                Err(diagnostic
+                    .with_category("naming")
+                    .with_code("duplicate-declaration"))
             }
             Entry::Vacant(entry) => {
                 entry.insert(ent);
@@ -89,6 +96,24 @@ impl OverloadedName {
     }
 }
 
+/// The parameter names of a subprogram body must conform to those of its declaration
+fn non_conforming_param(decl: &NamedEntity, body: &NamedEntity) -> Option<Diagnostic> {
+    let (decl_param, body_param) = decl.signature()?.first_renamed_param(body.signature()?)?;
+
+    let mut diagnostic = Diagnostic::error(
+        body_param.decl_pos()?,
+        format!(
+            "Parameter '{}' does not conform to '{}' of the subprogram declaration",
+            body_param.designator(),
+            decl_param.designator()
+        ),
+    );
+    if let Some(pos) = decl_param.decl_pos() {
+        diagnostic.add_related(pos, "Declared here");
+    }
+    Some(diagnostic)
+}
+
 #[derive(Clone, Debug)]
 /// Identically named entities
 pub enum NamedEntities {
@@ -287,12 +312,24 @@ impl<'a> Region<'a> {
         ent: Arc<NamedEntity>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) {
-        if ent.kind().is_deferred_constant() && self.kind != RegionKind::PackageDeclaration {
-            ent.error(
-                diagnostics,
-                "Deferred constants are only allowed in package declarations (not body)",
-            );
-            return;
+        if ent.kind().is_deferred_constant() {
+            match self.kind {
+                RegionKind::PackageDeclaration => {}
+                RegionKind::PackageBody => {
+                    ent.error(
+                        diagnostics,
+                        "Deferred constants are only allowed in package declarations (not body)",
+                    );
+                    return;
+                }
+                RegionKind::Other => {
+                    ent.error(
+                        diagnostics,
+                        format!("Constant '{}' must have a value here", ent.designator()),
+                    );
+                    return;
+                }
+            }
         };
 
         match self.entities.entry(ent.designator().clone()) {
@@ -416,11 +453,32 @@ impl<'a> Region<'a> {
             .add_context_visibility(visible_pos, &region.visibility);
     }
 
+    /// All named entities declared in this region
+    pub fn immediates(&self) -> impl Iterator<Item = &NamedEntities> {
+        self.entities.values()
+    }
+
     /// Lookup a named entity declared in this region
     pub fn lookup_immediate(&self, designator: &Designator) -> Option<&NamedEntities> {
         self.entities.get(designator)
     }
 
+    /// True if the named entity is declared by the package that an enclosing package body extends
+    pub fn is_declared_by_extended_package(&self, ent: &NamedEntity) -> bool {
+        if self.kind == RegionKind::PackageBody {
+            self.lookup_immediate(ent.designator())
+                .map(|visible| match visible {
+                    NamedEntities::Single(decl) => decl.id() == ent.id(),
+                    _ => false,
+                })
+                .unwrap_or(false)
+        } else {
+            self.parent
+                .map(|region| region.is_declared_by_extended_package(ent))
+                .unwrap_or(false)
+        }
+    }
+
     /// Lookup a named entity declared in this region or an enclosing region
     fn lookup_enclosing(&self, designator: &Designator) -> Option<&NamedEntities> {
         // We do not need to look in the enclosing region of the extended region
@@ -532,4 +590,6 @@ pub(super) fn duplicate_error(
     }
 
     diagnostic
+        .with_category("naming")
+        .with_code("duplicate-declaration")
 }
@@ -9,6 +9,7 @@ use std::collections::hash_map::Entry;
 
 use super::analyze::*;
 use super::lock::*;
+use super::metrics::*;
 use super::region::*;
 use crate::ast::search::*;
 use crate::ast::*;
@@ -17,15 +18,37 @@ use crate::syntax::Symbols;
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+/// How names which cannot be resolved are reported
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResolutionStrictness {
+    /// Every unresolved name is an error
+    Strict,
+    /// Names selected from a library are assumed to be valid when the primary unit is missing,
+    /// such as when analyzing a single file without the libraries it depends upon.
+    /// This applies to expressions, type marks and use clauses, a library clause
+    /// of a library which is not loaded is also assumed to be valid
+    Lenient,
+}
+
+/// The revision of the VHDL standard which is analyzed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VHDLStandard {
+    VHDL1993,
+    VHDL2008,
+}
+
 /// A design unit with design unit data
 pub(super) struct AnalysisData {
     pub diagnostics: Vec<Diagnostic>,
     pub has_circular_dependency: bool,
 
-    // Only for primary units
+    // For secondary units only the visibility of their own context clause
     pub root_region: Arc<Region<'static>>,
+    // Only for primary units
     pub region: Arc<Region<'static>>,
     pub ent: Option<Arc<NamedEntity>>,
+    // Only when metrics are enabled
+    pub timing: Option<UnitTiming>,
 }
 
 pub(super) type UnitReadGuard<'a> = ReadGuard<'a, AnyDesignUnit, AnalysisData>;
@@ -254,6 +277,19 @@ pub struct DesignRoot {
     // Tracks which units have a "use library.all;" clause.
     // library name  =>  set(affected)
     users_of_library_all: RwLock<FnvHashMap<Symbol, FnvHashSet<UnitId>>>,
+
+    // Opt-in checks which are not required by the LRM
+    lints: bool,
+
+    // Only the errors required by the LRM are reported regardless of lints
+    strict: bool,
+
+    // Opt-in timing of the analysis phases
+    metrics: bool,
+
+    resolution_strictness: ResolutionStrictness,
+
+    standard: VHDLStandard,
 }
 
 impl DesignRoot {
@@ -264,9 +300,97 @@ impl DesignRoot {
             users_of: RwLock::new(FnvHashMap::default()),
             missing_primary: RwLock::new(FnvHashMap::default()),
             users_of_library_all: RwLock::new(FnvHashMap::default()),
+            lints: false,
+            strict: false,
+            metrics: false,
+            resolution_strictness: ResolutionStrictness::Strict,
+            standard: VHDLStandard::VHDL2008,
+        }
+    }
+
+    /// Enable or disable opt-in checks that are not required by the LRM
+    pub fn enable_lints(&mut self, enable: bool) {
+        let was_enabled = self.lints_enabled();
+        self.lints = enable;
+        self.reset_if_lints_changed(was_enabled);
+    }
+
+    /// Only report the errors required by the LRM, opt-in lints stay disabled
+    /// even if they are enabled afterwards
+    pub fn enable_strict_mode(&mut self) {
+        let was_enabled = self.lints_enabled();
+        self.strict = true;
+        self.reset_if_lints_changed(was_enabled);
+    }
+
+    pub(super) fn lints_enabled(&self) -> bool {
+        self.lints && !self.strict
+    }
+
+    /// Discard the analysis of all units such that they are analyzed again
+    fn reset_all_units(&self) {
+        for library in self.libraries.values() {
+            for unit in library.units.values() {
+                unit.unit.reset();
+            }
         }
     }
 
+    fn reset_if_lints_changed(&self, was_enabled: bool) {
+        if self.lints_enabled() != was_enabled {
+            self.reset_all_units();
+        }
+    }
+
+    /// Set how names selected from a library with a missing primary unit are reported
+    pub fn set_resolution_strictness(&mut self, strictness: ResolutionStrictness) {
+        if self.resolution_strictness != strictness {
+            self.resolution_strictness = strictness;
+            self.reset_all_units();
+        }
+    }
+
+    pub(super) fn resolution_strictness(&self) -> ResolutionStrictness {
+        self.resolution_strictness
+    }
+
+    /// Set the revision of the VHDL standard, VHDL-2008 by default
+    pub fn set_standard(&mut self, standard: VHDLStandard) {
+        if self.standard != standard {
+            self.standard = standard;
+            self.reset_all_units();
+        }
+    }
+
+    pub(super) fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
+    /// Enable or disable timing of the analysis phases of each design unit
+    pub fn enable_metrics(&mut self, enable: bool) {
+        if self.metrics != enable {
+            self.metrics = enable;
+            self.reset_all_units();
+        }
+    }
+
+    /// The timing of each design unit, requires metrics to be enabled
+    pub fn metrics(&self) -> Metrics {
+        let mut metrics = Metrics::default();
+        for library in self.libraries.values() {
+            for unit_id in library.sorted_unit_ids() {
+                let unit = library.units.get(unit_id.key()).unwrap();
+                if let Some(timing) = self.get_analysis(unit).result().timing {
+                    metrics.units.push(UnitMetrics {
+                        unit_id: unit_id.clone(),
+                        timing,
+                    });
+                }
+            }
+        }
+        metrics
+    }
+
     /// Create library if it does not exist or return existing
     fn get_or_create_library(&mut self, name: Symbol) -> &mut Library {
         match self.libraries.entry(name) {
@@ -321,6 +445,191 @@ impl DesignRoot {
         FindAllReferences::search(self, decl_pos)
     }
 
+    /// Expand a 'use lib.pkg.all' clause within library_name into the names
+    /// it makes potentially visible, in the order they were declared
+    pub fn expand_use_all(
+        &self,
+        library_name: &Symbol,
+        use_clause: &UseClause,
+    ) -> Result<Vec<Designator>, Diagnostic> {
+        let mut designators = Vec::new();
+
+        for name in use_clause.name_list.iter() {
+            let (prefix_library, package_name) =
+                if let Some((Designator::Identifier(library), package_name)) =
+                    as_library_package_all(&name.item)
+                {
+                    if *library == self.symbol_utf8("work") {
+                        (library_name.clone(), package_name)
+                    } else {
+                        (library.clone(), package_name)
+                    }
+                } else {
+                    return Err(Diagnostic::error(name, "Expected 'library.package.all'"));
+                };
+
+            let unit = match package_name {
+                Designator::Identifier(ref package_name) => self
+                    .get_library_units(&prefix_library)
+                    .and_then(|units| units.get(&UnitKey::Primary(package_name.clone()))),
+                _ => None,
+            };
+
+            let unit = if let Some(unit) = unit {
+                unit
+            } else {
+                return Err(Diagnostic::error(
+                    name,
+                    format!(
+                        "No primary unit '{}' within library '{}'",
+                        package_name, prefix_library
+                    ),
+                ));
+            };
+
+            let data = self.get_analysis(unit);
+            match data.result().ent.as_ref().map(|ent| ent.kind()) {
+                Some(NamedEntityKind::Package(ref region))
+                | Some(NamedEntityKind::PackageInstance(ref region)) => {
+                    let mut visible: Vec<_> = region.immediates().collect();
+                    visible
+                        .sort_by_key(|visible| visible.first().decl_pos().map(|pos| pos.start()));
+                    designators.extend(
+                        visible
+                            .into_iter()
+                            .map(|visible| visible.designator().clone()),
+                    );
+                }
+                _ => {
+                    return Err(Diagnostic::error(
+                        name,
+                        format!("'{}' is not a package", package_name),
+                    ));
+                }
+            }
+        }
+
+        Ok(designators)
+    }
+
+    /// Selected names within the design unit at the cursor which do not resolve locally
+    /// such as work.other_pkg.name, these may be resolved by adding more files
+    pub fn external_references(&self, source: &Source, cursor: Position) -> Vec<(Symbol, SrcPos)> {
+        if let Some(unit) = self.unit_at_cursor(source, cursor) {
+            let data = self.get_analysis(unit);
+            FindExternalReferences::search(&*data)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// All names within the design unit at the cursor together with the declaration they resolve to
+    pub fn references(&self, source: &Source, cursor: Position) -> Vec<NameReference> {
+        if let Some(unit) = self.unit_at_cursor(source, cursor) {
+            let data = self.get_analysis(unit);
+            FindAllNameReferences::search(&*data)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Check if a name is visible within the design unit at the cursor, either implicitly such
+    /// as std.standard, through its context clause or through use clauses of the enclosing
+    /// declarative parts before the cursor
+    pub fn is_visible(&self, source: &Source, cursor: Position, name: &str) -> bool {
+        let unit = if let Some(unit) = self.unit_at_cursor(source, cursor) {
+            unit
+        } else {
+            return false;
+        };
+
+        let designator = Designator::Identifier(self.symbol_utf8(name));
+        let library_name = unit.unit_id().library_name();
+        let data = self.get_analysis(unit);
+        if data
+            .result()
+            .root_region
+            .lookup_within(unit.ident().pos(), &designator)
+            .is_ok()
+        {
+            return true;
+        }
+
+        let enclosing = enclosing_declarations(&data, &source.pos(cursor, cursor));
+        let is_used = enclosing
+            .iter()
+            .flat_map(|decl| decl.iter())
+            .any(|decl| match decl {
+                Declaration::Use(ref use_clause) if use_clause.pos.start() < cursor => {
+                    self.use_clause_makes_visible(library_name, &use_clause.item, &designator)
+                }
+                _ => false,
+            });
+        if is_used {
+            return true;
+        }
+
+        // The context clause and declarative part of the primary unit also apply to the secondary unit
+        if let AnyDesignUnit::Secondary(ref secondary) = *data {
+            if let Some(primary) = self
+                .get_library_units(library_name)
+                .and_then(|units| units.get(&UnitKey::Primary(secondary.primary_name().clone())))
+            {
+                let primary_data = self.get_analysis(primary);
+                if primary_data
+                    .result()
+                    .root_region
+                    .lookup_within(unit.ident().pos(), &designator)
+                    .is_ok()
+                {
+                    return true;
+                }
+
+                let primary_decl: &[Declaration] = match *primary_data {
+                    AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) => &entity.decl,
+                    AnyDesignUnit::Primary(AnyPrimaryUnit::Package(ref package)) => &package.decl,
+                    _ => &[],
+                };
+                return primary_decl.iter().any(|decl| match decl {
+                    Declaration::Use(ref use_clause) => {
+                        self.use_clause_makes_visible(library_name, &use_clause.item, &designator)
+                    }
+                    _ => false,
+                });
+            }
+        }
+
+        false
+    }
+
+    fn use_clause_makes_visible(
+        &self,
+        library_name: &Symbol,
+        use_clause: &UseClause,
+        designator: &Designator,
+    ) -> bool {
+        let is_selected = use_clause.name_list.iter().any(|name| match name.item {
+            Name::Selected(_, ref suffix) => suffix.item.item == *designator,
+            _ => false,
+        });
+
+        is_selected
+            || self
+                .expand_use_all(library_name, use_clause)
+                .map(|designators| designators.contains(designator))
+                .unwrap_or(false)
+    }
+
+    /// The last design unit within the source which starts before the cursor
+    fn unit_at_cursor(&self, source: &Source, cursor: Position) -> Option<&LockedUnit> {
+        self.libraries
+            .values()
+            .flat_map(|library| library.units.values())
+            .filter(|unit| unit.ident().pos().source == *source)
+            .filter(|unit| unit_start(&self.get_analysis(unit)) <= cursor)
+            .max_by_key(|unit| unit.ident().pos().start())
+    }
+
     pub(super) fn symbol_utf8(&self, name: &str) -> Symbol {
         self.symbols.symtab().insert_utf8(name)
     }
@@ -335,19 +644,43 @@ impl DesignRoot {
                 let mut root_region = Region::default();
                 let mut region = Region::default();
 
-                let has_circular_dependency = if let Err(err) = context.analyze_design_unit(
-                    entity_id,
-                    &mut *unit,
-                    &mut root_region,
-                    &mut region,
-                    &mut diagnostics,
-                ) {
+                let (result, analysis_time) = timed(self.metrics, || {
+                    context.analyze_design_unit(
+                        entity_id,
+                        &mut *unit,
+                        &mut root_region,
+                        &mut region,
+                        &mut diagnostics,
+                    )
+                });
+
+                let has_circular_dependency = if let Err(err) = result {
                     err.push_into(&mut diagnostics);
                     true
                 } else {
                     false
                 };
 
+                let ((), lint_time) = timed(self.metrics, || {
+                    if self.lints_enabled() && !has_circular_dependency {
+                        let standard = context.standard_package_analysis();
+                        let standard_decl: &[Declaration] = match standard.as_deref() {
+                            Some(AnyDesignUnit::Primary(AnyPrimaryUnit::Package(ref package))) => {
+                                &package.decl
+                            }
+                            _ => &[],
+                        };
+
+                        super::lint::check_empty_protected_types(&unit, &mut diagnostics);
+                        super::lint::check_null_only_branches(&unit, &mut diagnostics);
+                        super::lint::check_static_while_loops(&unit, &mut diagnostics);
+                        super::lint::check_case_coverage(&unit, standard_decl, &mut diagnostics);
+                        super::lint::check_parameter_shadowing(&unit, &mut diagnostics);
+                        super::lint::check_concurrent_procedure_calls(&unit, &mut diagnostics);
+                        super::lint::check_unbounded_recursion(&unit, &mut diagnostics);
+                    }
+                });
+
                 let root_region = Arc::new(root_region);
                 let region = Arc::new(region);
 
@@ -385,6 +718,8 @@ impl DesignRoot {
                     region,
                     ent,
                     has_circular_dependency,
+                    timing: analysis_time
+                        .and_then(|analysis| lint_time.map(|lint| UnitTiming { analysis, lint })),
                 };
 
                 unit.finish(result)
@@ -585,7 +920,67 @@ impl DesignRoot {
                 diagnostics.append(unit.unit.expect_analyzed().result().diagnostics.clone());
             }
         }
+
+        if self.lints_enabled() {
+            for library in self.libraries.values() {
+                self.lint_entities(library, diagnostics);
+            }
+        }
+    }
+
+    /// Lints which need an entity together with all of its architectures
+    fn lint_entities(&self, library: &Library, diagnostics: &mut dyn DiagnosticHandler) {
+        let units: Vec<_> = library
+            .sorted_unit_ids()
+            .iter()
+            .map(|unit_id| {
+                library
+                    .units
+                    .get(unit_id.key())
+                    .unwrap()
+                    .unit
+                    .expect_analyzed()
+            })
+            .collect();
+
+        for unit in units.iter() {
+            if let AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(ref entity)) = **unit {
+                let architectures: Vec<_> = units
+                    .iter()
+                    .filter_map(|unit| match **unit {
+                        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ref arch))
+                            if arch.entity_name.item.item == entity.ident.item =>
+                        {
+                            Some(arch)
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                super::lint::check_missing_architecture(entity, &architectures, diagnostics);
+                super::lint::check_undriven_outputs(entity, &architectures, diagnostics);
+            }
+        }
+    }
+}
+
+/// Start of the design unit including its context clause
+fn unit_start(unit: &AnyDesignUnit) -> Position {
+    unit.context_clauses()
+        .first()
+        .map(|item| item.pos.start())
+        .unwrap_or_else(|| unit.pos().start())
+}
+
+/// Split a 'library.package.all' name into its library and package designators
+fn as_library_package_all(name: &Name) -> Option<(&Designator, &Designator)> {
+    if let Name::SelectedAll(ref prefix) = name {
+        if let Name::Selected(ref library, ref package) = prefix.item {
+            if let Name::Designator(ref library) = library.item {
+                return Some((library.designator(), package.designator()));
+            }
+        }
     }
+    None
 }
 
 fn get_all_affected(
@@ -9,6 +9,7 @@ use super::region::*;
 use crate::ast::Range;
 use crate::ast::*;
 use crate::data::*;
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 pub enum ResolvedName {
@@ -68,6 +69,22 @@ impl<'a> AnalyzeContext<'a> {
                     ))
                 }
             }
+            NamedEntityKind::Object(Object {
+                subtype: Some(ref subtype),
+                ..
+            })
+            | NamedEntityKind::InterfaceObject(InterfaceObject { ref subtype, .. })
+                if subtype.base_type().actual_kind().is_scalar_type() =>
+            {
+                Err(AnalysisError::not_fatal_error(
+                    suffix.as_ref(),
+                    format!(
+                        "Cannot select '{}' from '{}'",
+                        suffix.item,
+                        prefix.designator()
+                    ),
+                ))
+            }
             _ => Ok(ResolvedName::Unknown),
         }
     }
@@ -138,7 +155,9 @@ impl<'a> AnalyzeContext<'a> {
                                 suffix.set_reference(&visible);
                                 Ok(Some(ResolvedName::Known(visible)))
                             }
-                            Ok(ResolvedName::Unknown) => Ok(Some(ResolvedName::Unknown)),
+                            Ok(ResolvedName::Unknown) | Err(AnalysisError::Unknown) => {
+                                Ok(Some(ResolvedName::Unknown))
+                            }
                             Err(err) => {
                                 err.add_to(diagnostics)?;
                                 Ok(None)
@@ -203,37 +222,8 @@ impl<'a> AnalyzeContext<'a> {
         kind_ok: &impl Fn(&NamedEntityKind) -> bool,
         expected: &str,
     ) -> AnalysisResult<Arc<NamedEntity>> {
-        match self
-            .resolve_selected_name(region, name)?
-            .into_non_overloaded()
-        {
-            Ok(ent) => {
-                if kind_ok(ent.actual_kind()) {
-                    Ok(ent)
-                } else {
-                    let mut error = Diagnostic::error(
-                        name.suffix_pos(),
-                        format!("Expected {}, got {}", expected, ent.describe()),
-                    );
-                    if let Some(pos) = ent.decl_pos() {
-                        error.add_related(pos, "Defined here");
-                    }
-                    Err(AnalysisError::NotFatal(error))
-                }
-            }
-            Err(overloaded) => {
-                let mut error = Diagnostic::error(
-                    name.suffix_pos(),
-                    format!("Expected {}, got overloaded name", expected),
-                );
-                for ent in overloaded.entities() {
-                    if let Some(pos) = ent.decl_pos() {
-                        error.add_related(pos, "Defined here");
-                    }
-                }
-                Err(AnalysisError::NotFatal(error))
-            }
-        }
+        let named_entities = self.resolve_selected_name(region, name)?;
+        expect_non_overloaded(name.suffix_pos(), named_entities, kind_ok, expected)
     }
 
     pub fn resolve_type_mark(
@@ -241,7 +231,27 @@ impl<'a> AnalyzeContext<'a> {
         region: &Region<'_>,
         type_mark: &mut WithPos<SelectedName>,
     ) -> AnalysisResult<Arc<NamedEntity>> {
-        self.resolve_non_overloaded(region, type_mark, &NamedEntityKind::is_type, "type")
+        let named_entities = self.resolve_selected_name(region, type_mark)?;
+
+        if let NamedEntities::Single(ref ent) = named_entities {
+            if ent.actual_kind().is_object() {
+                let mut error = Diagnostic::error(
+                    type_mark.suffix_pos(),
+                    format!("'{}' is an object, not a type", ent.designator()),
+                );
+                if let Some(pos) = ent.decl_pos() {
+                    error.add_related(pos, "Defined here");
+                }
+                return Err(AnalysisError::NotFatal(error));
+            }
+        }
+
+        expect_non_overloaded(
+            type_mark.suffix_pos(),
+            named_entities,
+            &NamedEntityKind::is_type,
+            "type",
+        )
     }
 
     fn analyze_attribute_name(
@@ -289,26 +299,32 @@ impl<'a> AnalyzeContext<'a> {
         Ok(())
     }
 
+    /// Returns the type of the discrete range when it has a type mark which could be resolved
     pub fn analyze_discrete_range(
         &self,
         region: &Region<'_>,
         drange: &mut DiscreteRange,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> FatalNullResult {
+    ) -> FatalResult<Option<Arc<NamedEntity>>> {
         match drange {
             DiscreteRange::Discrete(ref mut type_mark, ref mut range) => {
-                if let Err(err) = self.resolve_type_mark(region, type_mark) {
-                    err.add_to(diagnostics)?;
-                }
+                let typ = match self.resolve_type_mark(region, type_mark) {
+                    Ok(typ) => Some(typ),
+                    Err(err) => {
+                        err.add_to(diagnostics)?;
+                        None
+                    }
+                };
                 if let Some(ref mut range) = range {
                     self.analyze_range(region, range, diagnostics)?;
                 }
+                Ok(typ)
             }
             DiscreteRange::Range(ref mut range) => {
                 self.analyze_range(region, range, diagnostics)?;
+                Ok(None)
             }
         }
-        Ok(())
     }
 
     pub fn analyze_choices(
@@ -369,6 +385,10 @@ impl<'a> AnalyzeContext<'a> {
     ) -> FatalNullResult {
         for AssociationElement { actual, .. } in elems.iter_mut() {
             match actual.item {
+                // A port of mode linkage may be associated as a whole
+                ActualPart::Expression(Expression::Name(ref mut name)) => {
+                    self.resolve_name(region, &actual.pos, name, diagnostics)?;
+                }
                 ActualPart::Expression(ref mut expr) => {
                     self.analyze_expression_pos(region, &actual.pos, expr, diagnostics)?;
                 }
@@ -447,7 +467,13 @@ impl<'a> AnalyzeContext<'a> {
                 self.analyze_expression(region, inner, diagnostics)
             }
             Expression::Name(ref mut name) => {
-                self.resolve_name(region, pos, name, diagnostics)?;
+                if let Some(ResolvedName::Known(visible)) =
+                    self.resolve_name(region, pos, name, diagnostics)?
+                {
+                    if let Ok(ent) = visible.into_non_overloaded() {
+                        check_linkage_use(pos, &ent, diagnostics);
+                    }
+                }
                 Ok(())
             }
             Expression::Aggregate(ref mut assocs) => {
@@ -465,22 +491,112 @@ impl<'a> AnalyzeContext<'a> {
                     self.analyze_subtype_indication(region, subtype, diagnostics)
                 }
             },
+            Expression::Literal(Literal::Physical(_, ref unit)) => {
+                self.check_physical_unit(region, pos, unit, diagnostics);
+                Ok(())
+            }
             Expression::Literal(_) => Ok(()),
         }
     }
 
+    /// The unit of a physical literal must be a visible unit of a physical type
+    fn check_physical_unit(
+        &self,
+        region: &Region<'_>,
+        pos: &SrcPos,
+        unit: &Symbol,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_unit = match region.lookup_within(pos, &Designator::Identifier(unit.clone())) {
+            Ok(NamedEntities::Single(ent)) => match ent.actual_kind() {
+                NamedEntityKind::PhysicalLiteral => true,
+                _ => false,
+            },
+            Ok(NamedEntities::Overloaded(..)) | Err(..) => false,
+        };
+
+        if !is_unit {
+            diagnostics.error(pos, format!("Unknown physical unit '{}'", unit));
+        }
+    }
+
+    /// The base type of an expression which is the simple name of an object
+    /// @TODO other expressions are unknown until type checking exists
+    pub fn object_base_type(
+        &self,
+        region: &Region<'_>,
+        expr: &WithPos<Expression>,
+    ) -> Option<Arc<NamedEntity>> {
+        let designator = match expr.item {
+            Expression::Name(ref name) => match **name {
+                Name::Designator(ref designator) => &designator.item,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let subtype = match region.lookup_within(&expr.pos, designator) {
+            Ok(NamedEntities::Single(ent)) => match ent.actual_kind() {
+                NamedEntityKind::Object(Object {
+                    subtype: Some(ref subtype),
+                    ..
+                }) => subtype.clone(),
+                NamedEntityKind::InterfaceObject(ref object) => object.subtype.clone(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(subtype.base_type().clone())
+    }
+
+    /// The choices of a selected assignment must be of the type of the selector
+    /// @TODO only abstract literal choices of an enumeration selector are checked until type checking exists
+    fn check_selection_choices<T>(
+        &self,
+        region: &Region<'_>,
+        selection: &Selection<T>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_enumeration = match self.object_base_type(region, &selection.expression) {
+            Some(base_type) => match base_type.actual_kind() {
+                NamedEntityKind::ScalarType(ScalarClass::Enumeration, _) => true,
+                _ => false,
+            },
+            None => false,
+        };
+
+        if !is_enumeration {
+            return;
+        }
+
+        for alternative in selection.alternatives.iter() {
+            for choice in alternative.choices.iter() {
+                if let Choice::Expression(ref expr) = choice {
+                    if let Expression::Literal(Literal::AbstractLiteral(..)) = expr.item {
+                        diagnostics.error(
+                            expr,
+                            "Choice type does not match selector in selected assignment",
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     // @TODO maybe make generic function for expression/waveform.
     // wait until type checking to see if it makes sense
     pub fn analyze_expr_assignment(
         &self,
         region: &Region<'_>,
         target: &mut WithPos<Target>,
+        class: ObjectClass,
         rhs: &mut AssignmentRightHand<WithPos<Expression>>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         match rhs {
             AssignmentRightHand::Simple(expr) => {
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, class, diagnostics)?;
                 self.analyze_expression(region, expr, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
@@ -488,7 +604,7 @@ impl<'a> AnalyzeContext<'a> {
                     conditionals,
                     else_item,
                 } = conditionals;
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, class, diagnostics)?;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
                     self.analyze_expression(region, item, diagnostics)?;
@@ -505,11 +621,12 @@ impl<'a> AnalyzeContext<'a> {
                 } = selection;
                 self.analyze_expression(region, expression, diagnostics)?;
                 // target is located after expression
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, class, diagnostics)?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
                     self.analyze_expression(region, item, diagnostics)?;
                     self.analyze_choices(region, choices, diagnostics)?;
                 }
+                self.check_selection_choices(region, selection, diagnostics);
             }
         }
         Ok(())
@@ -524,7 +641,7 @@ impl<'a> AnalyzeContext<'a> {
     ) -> FatalNullResult {
         match rhs {
             AssignmentRightHand::Simple(wavf) => {
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, ObjectClass::Signal, diagnostics)?;
                 self.analyze_waveform(region, wavf, diagnostics)?;
             }
             AssignmentRightHand::Conditional(conditionals) => {
@@ -532,7 +649,7 @@ impl<'a> AnalyzeContext<'a> {
                     conditionals,
                     else_item,
                 } = conditionals;
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, ObjectClass::Signal, diagnostics)?;
                 for conditional in conditionals {
                     let Conditional { condition, item } = conditional;
                     self.analyze_waveform(region, item, diagnostics)?;
@@ -549,25 +666,62 @@ impl<'a> AnalyzeContext<'a> {
                 } = selection;
                 self.analyze_expression(region, expression, diagnostics)?;
                 // target is located after expression
-                self.analyze_target(region, target, diagnostics)?;
+                self.analyze_target(region, target, ObjectClass::Signal, diagnostics)?;
                 for Alternative { choices, item } in alternatives.iter_mut() {
                     self.analyze_waveform(region, item, diagnostics)?;
                     self.analyze_choices(region, choices, diagnostics)?;
                 }
+                self.check_selection_choices(region, selection, diagnostics);
             }
         }
         Ok(())
     }
 
+    /// The class is the class of object assigned by the statement, either a signal or a variable
     pub fn analyze_target(
         &self,
         parent: &Region<'_>,
         target: &mut WithPos<Target>,
+        class: ObjectClass,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
         match target.item {
             Target::Name(ref mut name) => {
-                self.resolve_name(parent, &target.pos, name, diagnostics)?;
+                match self.resolve_name(parent, &target.pos, name, diagnostics)? {
+                    Some(ResolvedName::Known(visible)) => {
+                        if let Ok(ent) = visible.into_non_overloaded() {
+                            check_assignment_class(&target.pos, &ent, class, diagnostics);
+                            if is_constant(&ent) {
+                                diagnostics.error(
+                                    &target.pos,
+                                    format!("Cannot assign to constant '{}'", ent.designator()),
+                                );
+                            }
+                            check_linkage_use(&target.pos, &ent, diagnostics);
+                            check_package_signal_assignment(
+                                parent,
+                                &target.pos,
+                                &ent,
+                                class,
+                                diagnostics,
+                            );
+                        }
+                    }
+                    Some(ResolvedName::Unknown) => {
+                        if let Some(ent) = self.resolve_target_base(parent, &target.pos, name)? {
+                            if is_constant(&ent) {
+                                diagnostics.error(
+                                    &target.pos,
+                                    format!(
+                                        "Cannot assign to part of constant '{}'",
+                                        ent.designator()
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    None => {}
+                }
             }
             Target::Aggregate(ref mut assocs) => {
                 self.analyze_aggregate(parent, assocs, diagnostics)?;
@@ -575,6 +729,188 @@ impl<'a> AnalyzeContext<'a> {
         }
         Ok(())
     }
+
+    /// The object of which an indexed, sliced or selected name such as `c(0).elem` is a part
+    fn resolve_target_base(
+        &self,
+        region: &Region<'_>,
+        name_pos: &SrcPos,
+        name: &mut Name,
+    ) -> FatalResult<Option<Arc<NamedEntity>>> {
+        match name {
+            Name::Indexed(ref mut prefix, ..) | Name::Slice(ref mut prefix, ..) => {
+                self.resolve_target_base(region, &prefix.pos, &mut prefix.item)
+            }
+            // An indexed name is parsed as a function call when its prefix is not known to be an object
+            Name::FunctionCall(ref mut fcall) => {
+                self.resolve_target_base(region, &fcall.name.pos, &mut fcall.name.item)
+            }
+            Name::Designator(..) | Name::Selected(..) => {
+                // Any errors were already reported when the whole target was resolved
+                if let Some(ResolvedName::Known(visible)) =
+                    self.resolve_name(region, name_pos, name, &mut Vec::new())?
+                {
+                    if let Ok(ent) = visible.into_non_overloaded() {
+                        if ent.actual_kind().is_object() {
+                            return Ok(Some(ent));
+                        }
+                    }
+                }
+
+                // A record element selected from an object
+                if let Name::Selected(ref mut prefix, _) = name {
+                    self.resolve_target_base(region, &prefix.pos, &mut prefix.item)
+                } else {
+                    Ok(None)
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+fn is_constant(ent: &NamedEntity) -> bool {
+    match ent.actual_kind() {
+        NamedEntityKind::Object(object) => object.class == ObjectClass::Constant,
+        NamedEntityKind::InterfaceObject(object) => object.class == ObjectClass::Constant,
+        NamedEntityKind::DeferredConstant => true,
+        _ => false,
+    }
+}
+
+fn expect_non_overloaded(
+    pos: &SrcPos,
+    named_entities: NamedEntities,
+    kind_ok: &impl Fn(&NamedEntityKind) -> bool,
+    expected: &str,
+) -> AnalysisResult<Arc<NamedEntity>> {
+    match named_entities.into_non_overloaded() {
+        Ok(ent) => {
+            if kind_ok(ent.actual_kind()) {
+                Ok(ent)
+            } else {
+                let mut error = Diagnostic::error(
+                    pos,
+                    format!("Expected {}, got {}", expected, ent.describe()),
+                );
+                if let Some(pos) = ent.decl_pos() {
+                    error.add_related(pos, "Defined here");
+                }
+                Err(AnalysisError::NotFatal(error))
+            }
+        }
+        Err(overloaded) => {
+            let mut error =
+                Diagnostic::error(pos, format!("Expected {}, got overloaded name", expected));
+            for ent in overloaded.entities() {
+                if let Some(pos) = ent.decl_pos() {
+                    error.add_related(pos, "Defined here");
+                }
+            }
+            Err(AnalysisError::NotFatal(error))
+        }
+    }
+}
+
+/// The value of a possibly negated integer literal
+pub(super) fn static_integer(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+            i64::try_from(*value).ok()
+        }
+        Expression::Unary(Unary::Minus, ref operand) => {
+            static_integer(&operand.item).and_then(|value| value.checked_neg())
+        }
+        _ => None,
+    }
+}
+
+/// True if the bounds of the range are integer literals and the range is null
+pub(super) fn is_static_null_range(constraint: &RangeConstraint) -> Option<bool> {
+    let left = static_integer(&constraint.left_expr.item)?;
+    let right = static_integer(&constraint.right_expr.item)?;
+    match constraint.direction {
+        Direction::Ascending => Some(left > right),
+        Direction::Descending => Some(left < right),
+    }
+}
+
+fn check_assignment_class(
+    target_pos: &SrcPos,
+    ent: &NamedEntity,
+    class: ObjectClass,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    let target_class = match ent.actual_kind() {
+        NamedEntityKind::Object(object) => object.class,
+        NamedEntityKind::InterfaceObject(object) => object.class,
+        kind => {
+            if kind.is_type() {
+                diagnostics.error(
+                    target_pos,
+                    format!("'{}' is a type, not an object", ent.designator()),
+                );
+            }
+            return;
+        }
+    };
+
+    match (class, target_class) {
+        (ObjectClass::Variable, ObjectClass::Signal) => diagnostics.error(
+            target_pos,
+            format!("Use '<=' to assign signal '{}'", ent.designator()),
+        ),
+        (ObjectClass::Signal, ObjectClass::Variable)
+        | (ObjectClass::Signal, ObjectClass::SharedVariable) => diagnostics.error(
+            target_pos,
+            format!("Use ':=' to assign variable '{}'", ent.designator()),
+        ),
+        _ => {}
+    }
+}
+
+/// A port of mode linkage may not be read or updated other than by association as a whole
+fn check_linkage_use(pos: &SrcPos, ent: &NamedEntity, diagnostics: &mut dyn DiagnosticHandler) {
+    if let NamedEntityKind::InterfaceObject(ref object) = ent.actual_kind() {
+        if object.mode == Mode::Linkage {
+            let mut diagnostic = Diagnostic::error(
+                pos,
+                format!(
+                    "Port '{}' of mode linkage may only be associated as a whole",
+                    ent.designator()
+                ),
+            );
+            if let Some(decl_pos) = ent.decl_pos() {
+                diagnostic.add_related(decl_pos, "Declared here");
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+/// Signals declared in a package may not be assigned within its package body
+fn check_package_signal_assignment(
+    region: &Region<'_>,
+    target_pos: &SrcPos,
+    ent: &NamedEntity,
+    class: ObjectClass,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if class != ObjectClass::Signal {
+        return;
+    }
+
+    if let NamedEntityKind::Object(object) = ent.actual_kind() {
+        if object.class == ObjectClass::Signal && region.is_declared_by_extended_package(ent) {
+            diagnostics.error(
+                target_pos,
+                format!(
+                    "Cannot assign package signal '{}' in package body",
+                    ent.designator()
+                ),
+            );
+        }
+    }
 }
 
 // @TODO make method
@@ -20,10 +20,6 @@ impl<'a> AnalyzeContext<'a> {
         statement: &mut LabeledSequentialStatement,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalNullResult {
-        if let Some(ref label) = statement.label {
-            parent.add(label.clone(), NamedEntityKind::Label, diagnostics);
-        }
-
         match statement.statement {
             SequentialStatement::Return(ref mut ret) => {
                 let ReturnStatement { expression } = ret;
@@ -111,8 +107,10 @@ impl<'a> AnalyzeContext<'a> {
                     is_matching: _,
                     expression,
                     alternatives,
+                    end_pos: _,
                 } = case_stmt;
                 self.analyze_expression(parent, expression, diagnostics)?;
+                self.check_case_expression(parent, expression, diagnostics);
                 for alternative in alternatives.iter_mut() {
                     let Alternative { choices, item } = alternative;
                     self.analyze_choices(parent, choices, diagnostics)?;
@@ -131,7 +129,10 @@ impl<'a> AnalyzeContext<'a> {
                         let designator: WithPos<Designator> = index.clone().into();
                         region.add(
                             designator,
-                            NamedEntityKind::Object(ObjectClass::Constant),
+                            NamedEntityKind::Object(Object {
+                                class: ObjectClass::Constant,
+                                subtype: None,
+                            }),
                             diagnostics,
                         );
                         self.analyze_sequential_part(&mut region, statements, diagnostics)?;
@@ -155,7 +156,13 @@ impl<'a> AnalyzeContext<'a> {
             }
             SequentialStatement::VariableAssignment(ref mut assign) => {
                 let VariableAssignment { target, rhs } = assign;
-                self.analyze_expr_assignment(parent, target, rhs, diagnostics)?;
+                self.analyze_expr_assignment(
+                    parent,
+                    target,
+                    ObjectClass::Variable,
+                    rhs,
+                    diagnostics,
+                )?;
             }
             SequentialStatement::SignalForceAssignment(ref mut assign) => {
                 let SignalForceAssignment {
@@ -163,20 +170,117 @@ impl<'a> AnalyzeContext<'a> {
                     force_mode: _,
                     rhs,
                 } = assign;
-                self.analyze_expr_assignment(parent, target, rhs, diagnostics)?;
+                self.analyze_expr_assignment(
+                    parent,
+                    target,
+                    ObjectClass::Signal,
+                    rhs,
+                    diagnostics,
+                )?;
             }
             SequentialStatement::SignalReleaseAssignment(ref mut assign) => {
                 let SignalReleaseAssignment {
                     target,
                     force_mode: _,
                 } = assign;
-                self.analyze_target(parent, target, diagnostics)?;
+                self.analyze_target(parent, target, ObjectClass::Signal, diagnostics)?;
             }
             SequentialStatement::Null => {}
         }
         Ok(())
     }
 
+    /// Labels of sequential statements are implicitly declared in the innermost
+    /// enclosing process or subprogram, also when nested within loops
+    pub fn define_labels_for_sequential_part(
+        &self,
+        region: &mut Region<'_>,
+        statements: &[LabeledSequentialStatement],
+        enclosing: Option<&WithPos<Designator>>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        for statement in statements.iter() {
+            if let Some(ref label) = statement.label {
+                if let Some(enclosing) = enclosing {
+                    if enclosing.item == Designator::Identifier(label.item.clone()) {
+                        diagnostics.push(
+                            Diagnostic::error(
+                                label,
+                                format!("Duplicate declaration of '{}'", label.item),
+                            )
+                            .related(enclosing, "Previously defined here")
+                            .with_category("naming")
+                            .with_code("duplicate-declaration"),
+                        );
+                    }
+                }
+                region.add(label.clone(), NamedEntityKind::Label, diagnostics);
+            }
+
+            match statement.statement {
+                SequentialStatement::If(ref ifstmt) => {
+                    for conditional in ifstmt.conditionals.iter() {
+                        self.define_labels_for_sequential_part(
+                            region,
+                            &conditional.item,
+                            enclosing,
+                            diagnostics,
+                        );
+                    }
+                    if let Some(ref else_item) = ifstmt.else_item {
+                        self.define_labels_for_sequential_part(
+                            region,
+                            else_item,
+                            enclosing,
+                            diagnostics,
+                        );
+                    }
+                }
+                SequentialStatement::Case(ref case_stmt) => {
+                    for alternative in case_stmt.alternatives.iter() {
+                        self.define_labels_for_sequential_part(
+                            region,
+                            &alternative.item,
+                            enclosing,
+                            diagnostics,
+                        );
+                    }
+                }
+                SequentialStatement::Loop(ref loop_stmt) => {
+                    self.define_labels_for_sequential_part(
+                        region,
+                        &loop_stmt.statements,
+                        enclosing,
+                        diagnostics,
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The case expression must be of a discrete type or a one-dimensional character array
+    /// @TODO only the simple name of an object is checked until type checking exists
+    fn check_case_expression(
+        &self,
+        region: &Region<'_>,
+        expression: &WithPos<Expression>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_discrete = match self.object_base_type(region, expression) {
+            Some(base_type) => match base_type.actual_kind() {
+                NamedEntityKind::ScalarType(class, _) => class.is_discrete(),
+                NamedEntityKind::RecordType | NamedEntityKind::ProtectedType(..) => false,
+                _ => true,
+            },
+            None => true,
+        };
+
+        if !is_discrete {
+            diagnostics.error(expression, "Case expression must be of a discrete type");
+        }
+    }
+
     pub fn analyze_sequential_part(
         &self,
         parent: &mut Region<'_>,
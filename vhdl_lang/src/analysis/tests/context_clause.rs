@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::ast::Designator;
 
 #[test]
 fn check_library_clause_library_exists() {
@@ -132,6 +133,76 @@ end context;
     )
 }
 
+#[test]
+fn hint_on_repeated_library_clause_and_context_reference_in_context_declaration() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname2",
+        "
+context ctx2 is
+end context;
+        ",
+    );
+    let code = builder.code(
+        "libname",
+        "
+context ctx is
+  library libname2;
+  library libname2;
+  context libname2.ctx2;
+  context libname2.ctx2;
+end context;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::hint(
+                code.s("libname2", 2),
+                "Duplicate library clause of 'libname2'",
+            )
+            .related(code.s("libname2", 1), "Previously made visible here"),
+            Diagnostic::hint(
+                code.s("libname2.ctx2", 2),
+                "Duplicate context reference of 'libname2.ctx2'",
+            )
+            .related(code.s("libname2.ctx2", 1), "Previously referenced here"),
+        ],
+    )
+}
+
+#[test]
+fn hint_on_repeated_context_reference_in_context_clause() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+context ctx is
+end context;
+
+library libname;
+context work.ctx;
+context libname.ctx;
+package pkg is
+end package;
+        ",
+    );
+
+    let diagnostics = builder.analyze();
+
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::hint(
+            code.s1("libname.ctx"),
+            "Duplicate context reference of 'libname.ctx'",
+        )
+        .related(code.s1("work.ctx"), "Previously referenced here")],
+    )
+}
+
 // This test was added to fix an accidental mistake when refactoring
 #[test]
 fn context_clause_does_change_work_symbol_meaning() {
@@ -1038,3 +1109,142 @@ end package;
 ",
     );
 }
+
+#[test]
+fn expand_use_all_returns_declared_names() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type enum_t is (alpha, beta);
+  constant c0 : natural := 0;
+  function fun return natural;
+end package;
+
+use work.pkg.all;
+use work.missing.all;
+package pkg2 is
+end package;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    assert_eq!(diagnostics.len(), 1);
+
+    let libname = code.symbol("libname");
+    let designators = root
+        .expand_use_all(&libname, &code.s1("use work.pkg.all;").use_clause().item)
+        .unwrap();
+    assert_eq!(
+        designators,
+        ["enum_t", "alpha", "beta", "c0", "fun"]
+            .iter()
+            .map(|name| Designator::Identifier(code.symbol(name)))
+            .collect::<Vec<_>>()
+    );
+
+    let missing_use = code.s1("use work.missing.all;");
+    assert_eq!(
+        root.expand_use_all(&libname, &missing_use.use_clause().item),
+        Err(Diagnostic::error(
+            missing_use.s1("work.missing.all"),
+            "No primary unit 'missing' within library 'libname'"
+        ))
+    );
+}
+
+#[test]
+fn is_visible_honors_implicit_and_explicit_context() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "ieee",
+        "
+package std_logic_1164 is
+  type std_logic is ('0', '1');
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+entity ent1 is
+end entity;
+
+library ieee;
+use ieee.std_logic_1164.all;
+entity ent2 is
+end entity;
+
+architecture rtl of ent2 is
+begin
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let source = code.source();
+    let ent1 = code.s1("ent1").start();
+    assert!(root.is_visible(source, ent1, "integer"));
+    assert!(!root.is_visible(source, ent1, "std_logic"));
+
+    let use_clause = code.s1("use ieee").start();
+    assert!(root.is_visible(source, use_clause, "std_logic"));
+    assert!(root.is_visible(source, code.s1("ent2").start(), "std_logic"));
+    assert!(root.is_visible(source, code.s1("rtl").start(), "std_logic"));
+}
+
+#[test]
+fn is_visible_honors_use_clauses_in_enclosing_declarative_parts() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type std_logic is ('0', '1');
+  constant zero : std_logic := '0';
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  use work.pkg.zero;
+end entity;
+
+architecture rtl of ent is
+  signal sig_before : bit;
+  use work.pkg.all;
+  signal sig_after : bit;
+begin
+  main : process
+  begin
+    wait;
+  end process;
+end architecture;
+
+architecture rtl2 of ent is
+begin
+  main2 : process is
+    use work.pkg.std_logic;
+  begin
+    wait;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let source = code.source();
+    assert!(!root.is_visible(source, code.s1("sig_before").start(), "std_logic"));
+    assert!(root.is_visible(source, code.s1("sig_after").start(), "std_logic"));
+    assert!(root.is_visible(source, code.s1("wait").start(), "std_logic"));
+
+    // The use clause of the entity applies to all architectures
+    let wait2 = code.s("wait", 2).start();
+    assert!(root.is_visible(source, wait2, "zero"));
+    assert!(root.is_visible(source, wait2, "std_logic"));
+    assert!(!root.is_visible(source, code.s1("main2").start(), "std_logic"));
+}
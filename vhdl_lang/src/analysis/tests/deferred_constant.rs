@@ -150,3 +150,33 @@ end package body;
     let diagnostics = builder.analyze();
     check_diagnostics(diagnostics, vec![duplicate(&code, "a1", 2, 3)]);
 }
+
+#[test]
+fn forbid_constant_without_value_outside_of_package() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant a1 : natural;
+begin
+  process
+    constant b1 : natural;
+  begin
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("a1"), "Constant 'a1' must have a value here"),
+            Diagnostic::error(code.s1("b1"), "Constant 'b1' must have a value here"),
+        ],
+    );
+}
@@ -0,0 +1,57 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{DiagnosticCallback, Severity};
+
+#[test]
+fn callback_receives_surviving_diagnostics_of_analysis_in_order() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib1",
+        "
+package pkg is
+  constant a : natural := 0;
+  constant a : natural := 0;
+  constant b : natural := 0;
+  constant b : natural := 0;
+end package;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp1 is
+  end component;
+begin
+end architecture;
+",
+    );
+    // The same source analyzed in two libraries reports each duplicate twice
+    builder.add_code("lib2", code.clone());
+    builder.enable_lints();
+
+    let mut received = Vec::new();
+    {
+        let mut callback =
+            DiagnosticCallback::new(Severity::Error, |diagnostic| received.push(diagnostic));
+        builder.analyze_into(&mut callback);
+    }
+
+    // The warning about the component is below the minimum severity
+    let messages: Vec<_> = received
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Duplicate declaration of 'a'",
+            "Duplicate declaration of 'b'"
+        ]
+    );
+    assert_eq!(received[0].pos, code.s("a :", 2).s1("a").pos());
+}
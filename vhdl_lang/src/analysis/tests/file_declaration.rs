@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn allows_file_with_open_kind_and_logical_name() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  type file_t is file of character;
+  file f0 : file_t;
+  file f1 : file_t is \"name\";
+  file f2 : file_t open read_mode is \"name\";
+end package;
+",
+    );
+}
+
+#[test]
+fn open_kind_requires_logical_name() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type file_t is file of character;
+  file f0 : file_t open write_mode;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("f0"),
+            "File 'f0' requires a logical name for this open mode",
+        )],
+    );
+}
@@ -5,6 +5,9 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::analysis::named_entity::NamedEntityKind;
+use crate::analysis::region::Region;
+use crate::data::{codes_present, find_homographs, messages_with_code};
 
 #[test]
 fn allows_unique_names() {
@@ -42,6 +45,26 @@ end package;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homographs_of_groups() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  signal s1, s2 : bit;
+  group gt1 is (signal, signal);
+  group gt1 is (signal <>);
+  group g1 : gt1 (s1, s2);
+  group g1 : gt1 (s2, s1);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["gt1", "g1"]));
+}
+
 #[test]
 fn forbid_homographs_in_subprogram_bodies() {
     let mut builder = LibraryBuilder::new();
@@ -147,6 +170,50 @@ end package;
     check_diagnostics(diagnostics, duplicates(&code, &["a1", "b1"]));
 }
 
+#[test]
+fn forbid_homograph_subprograms_in_protected_type_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+type prot_t is protected
+use work.pkg2.all;
+attribute attr of proc : procedure is 0;
+procedure proc(a : natural);
+procedure proc(b : natural);
+procedure proc(a : boolean);
+function fun return natural;
+function fun return boolean;
+end protected;
+end package;
+
+package body pkg is
+type prot_t is protected body
+end protected body;
+end package body;
+
+package pkg2 is
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("proc(b").s1("proc"),
+            "Duplicate declaration of 'proc' with signature [INTEGER]",
+        )
+        .related(
+            code.s1("proc(a : natural").s1("proc"),
+            "Previously defined here",
+        )
+        .with_category("naming")
+        .with_code("duplicate-declaration")],
+    );
+}
+
 #[test]
 fn forbid_homographs_in_subprogram_declarations() {
     let mut builder = LibraryBuilder::new();
@@ -220,6 +287,38 @@ end entity;
     check_diagnostics(diagnostics, duplicates(&code, &["a1", "b1"]));
 }
 
+#[test]
+fn forbid_homographs_in_block_header_and_declarations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+blk : block
+  generic (
+    g1 : natural;
+    g : natural;
+    g1 : natural);
+  generic map (
+    g1 => 0,
+    g => 0);
+  constant c1 : natural := 0;
+  constant c : natural := 0;
+  constant c1 : natural := 0;
+begin
+end block;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["g1", "c1"]));
+}
+
 #[test]
 fn forbid_homographs_in_process() {
     let mut builder = LibraryBuilder::new();
@@ -242,6 +341,33 @@ end entity;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homographs_of_variables_in_labeled_process() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+main : process
+  variable a1 : natural := 0;
+  variable a : natural := 0;
+  variable a1 : natural := 0;
+begin
+  wait on sig;
+end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
+}
+
 #[test]
 fn forbid_homographs_for_generate() {
     let mut builder = LibraryBuilder::new();
@@ -332,6 +458,35 @@ end entity;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homographs_of_signals_in_nested_for_generate() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  gen_outer: for i in 0 to 3 generate
+    signal sig1 : bit;
+    signal sig : bit;
+    signal sig1 : bit;
+  begin
+    gen_inner: for j in 0 to 1 generate
+      signal j : bit;
+    begin
+    end generate;
+  end generate;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["sig1", "j"]));
+}
+
 #[test]
 fn forbid_homographs_case_generate() {
     let mut builder = LibraryBuilder::new();
@@ -503,6 +658,57 @@ end package;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homographs_of_package_instances_in_package_body() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+generic (foo : natural);
+end package;
+
+package pkg is
+end package;
+
+package body pkg is
+constant a1 : natural := 0;
+package a1 is new work.gpkg generic map (foo => 0);
+package a2 is new work.gpkg generic map (foo => 0);
+package a2 is new work.gpkg generic map (foo => 1);
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["a1", "a2"]));
+}
+
+#[test]
+fn forbid_homographs_of_package_instances_in_architecture() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+generic (foo : natural);
+end package;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  package a1 is new work.gpkg generic map (foo => 0);
+  package a1 is new work.gpkg generic map (foo => 1);
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
+}
+
 #[test]
 fn forbid_homographs_in_attribute_declarations() {
     let mut builder = LibraryBuilder::new();
@@ -520,6 +726,30 @@ end package;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn attribute_specifications_are_not_homographs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+  attribute a1 : string;
+  attribute a1 : string;
+  attribute a2 : string;
+  attribute a2 of sig : signal is \"value\";
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
+}
+
 #[test]
 fn forbid_homographs_in_alias_declarations() {
     let mut builder = LibraryBuilder::new();
@@ -548,6 +778,65 @@ end package pkg;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_homographs_of_aliases_with_subtype_indication() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+constant x : natural := 0;
+constant y : natural := 0;
+
+alias a1 : natural is x;
+alias a1 : natural is y;
+
+constant c1 : natural := 0;
+alias c1 : natural is x;
+
+function \"-\"(a, b : bit) return bit;
+function \"*\"(a : bit) return bit;
+
+-- Legal since operator symbols are overloaded
+alias \"*\" is \"-\"[bit, bit return bit];
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, duplicates(&code, &["a1", "c1"]));
+}
+
+#[test]
+fn forbid_homographs_of_aliased_objects() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig0 : natural;
+  signal sig1 : natural;
+  signal other_sig : natural;
+  alias sig0 is other_sig;
+  alias sig1 is sig1;
+  alias sig2 is other_sig;
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    let mut expected = duplicates(&code, &["sig0"]);
+    expected.push(Diagnostic::error(
+        code.s("sig1", 2),
+        "Alias 'sig1' shadows itself",
+    ));
+    check_diagnostics(diagnostics, expected);
+}
+
 #[test]
 fn forbid_homographs_for_overloaded_vs_non_overloaded() {
     let mut builder = LibraryBuilder::new();
@@ -590,6 +879,41 @@ end package;
     check_no_diagnostics(&diagnostics);
 }
 
+#[test]
+fn forbid_homographs_within_enum_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+type color_t is (red, green, red);
+type char_t is ('a', red, 'b', 'a');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("red", 2),
+                "Duplicate declaration of 'red' with signature [return color_t]",
+            )
+            .related(code.s("red", 1), "Previously defined here")
+            .with_category("naming")
+            .with_code("duplicate-declaration"),
+            Diagnostic::error(
+                code.s("'a'", 2),
+                "Duplicate declaration of ''a'' with signature [return char_t]",
+            )
+            .related(code.s("'a'", 1), "Previously defined here")
+            .with_category("naming")
+            .with_code("duplicate-declaration"),
+        ],
+    );
+}
+
 #[test]
 fn forbid_homograph_to_enum_literals() {
     let mut builder = LibraryBuilder::new();
@@ -608,6 +932,34 @@ end package pkg;
     check_diagnostics(diagnostics, duplicates(&code, &["a1"]));
 }
 
+#[test]
+fn forbid_parameterless_function_homograph_to_enum_literal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+type enum_t is (alpha, beta);
+function alpha return enum_t;
+-- Ok since the return type differs
+function beta return natural;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("alpha", 2),
+            "Duplicate declaration of 'alpha' with signature [return enum_t]",
+        )
+        .related(code.s("alpha", 1), "Previously defined here")
+        .with_category("naming")
+        .with_code("duplicate-declaration")],
+    );
+}
+
 #[test]
 fn homograph_of_enum_literal_declared_by_alias() {
     let mut builder = LibraryBuilder::new();
@@ -627,7 +979,9 @@ end package;
 
     let diagnostics = builder.analyze();
     let error = Diagnostic::error(code.s("alpha", 2), "Duplicate declaration of 'alpha'")
-        .related(code.s("alias_t", 1), "Previously defined here");
+        .related(code.s("alias_t", 1), "Previously defined here")
+        .with_category("naming")
+        .with_code("duplicate-declaration");
     check_diagnostics(diagnostics, vec![error]);
 }
 
@@ -925,16 +1279,36 @@ end package body;
                 code.s("name1", 2),
                 "Duplicate declaration of 'name1' with signature [return NATURAL]",
             )
-            .related(code.s("name1", 1), "Previously defined here"),
+            .related(code.s("name1", 1), "Previously defined here")
+            .with_category("naming")
+            .with_code("duplicate-declaration"),
             Diagnostic::error(
                 code.s("name2", 2),
                 "Duplicate declaration of 'name2' with signature [STRING, return BOOLEAN]",
             )
-            .related(code.s("name2", 1), "Previously defined here"),
+            .related(code.s("name2", 1), "Previously defined here")
+            .with_category("naming")
+            .with_code("duplicate-declaration"),
         ],
     );
 }
 
+#[test]
+fn overloaded_with_distinct_signatures_are_not_homographs() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  function f(x : natural) return natural;
+  function f(x : boolean) return boolean;
+  function g return natural;
+  function g return boolean;
+  procedure p(x : natural);
+  procedure p(x : natural; y : boolean);
+end package;
+",
+    );
+}
+
 #[test]
 fn overloaded_declaration_is_not_homograph_with_definition() {
     check_code_with_no_diagnostics(
@@ -953,14 +1327,82 @@ end package body;
 }
 
 #[test]
-fn overloaded_alias_with_identical_signatures_are_homographs() {
-    let mut builder = LibraryBuilder::new();
-    let code = builder.code(
-        "libname",
+fn subprogram_body_with_conforming_parameter_names() {
+    check_code_with_no_diagnostics(
         "
-entity ent is
-end entity;
-
+package pkg is
+  procedure proc(a, b : natural);
+  function fun(c : natural) return natural;
+end package;
+
+package body pkg is
+  procedure proc(A, b : natural) is
+  begin
+  end;
+
+  function fun(c : natural) return natural is
+  begin
+    return c;
+  end;
+end package body;
+",
+    );
+}
+
+#[test]
+fn error_on_subprogram_body_with_renamed_parameter() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure proc(a, b : natural);
+end package;
+
+package body pkg is
+  procedure proc(a, c : natural) is
+  begin
+  end;
+
+  procedure local(d : natural);
+  procedure local(e : natural) is
+  begin
+  end;
+
+  procedure dup(f, f : natural) is
+  begin
+  end;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    let mut expected = vec![
+        Diagnostic::error(
+            code.s1("c : natural").s1("c"),
+            "Parameter 'c' does not conform to 'b' of the subprogram declaration",
+        )
+        .related(code.s1("b : natural").s1("b"), "Declared here"),
+        Diagnostic::error(
+            code.s1("e : natural").s1("e"),
+            "Parameter 'e' does not conform to 'd' of the subprogram declaration",
+        )
+        .related(code.s1("d : natural").s1("d"), "Declared here"),
+    ];
+    // The interface list of the body itself must also be free of homographs
+    expected.push(duplicate(&code, "f", 1, 2));
+    check_diagnostics(diagnostics, expected);
+}
+
+#[test]
+fn overloaded_alias_with_identical_signatures_are_homographs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
 architecture a of ent is
   function f1 return natural is
   begin
@@ -987,6 +1429,455 @@ end architecture;
             code.s("homo1", 2),
             "Duplicate declaration of 'homo1' with signature [return NATURAL]",
         )
-        .related(code.s("homo1", 1), "Previously defined here")],
+        .related(code.s("homo1", 1), "Previously defined here")
+        .with_category("naming")
+        .with_code("duplicate-declaration")],
+    );
+}
+
+#[test]
+fn sequential_labels_are_homographs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process is
+  begin
+    lab1 : for i in 0 to 1 loop
+      lab2 : for j in 0 to 1 loop
+      end loop;
+    end loop;
+
+    lab2 : for i in 0 to 1 loop
+    end loop;
+
+    main : loop
+    end loop;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            duplicate(&code, "lab2", 1, 2),
+            duplicate(&code, "main", 1, 2),
+        ],
+    );
+}
+
+#[test]
+fn allows_distinct_sequential_labels() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  procedure proc is
+  begin
+    lab1 : for i in 0 to 1 loop
+    end loop;
+  end procedure;
+begin
+  main : process is
+  begin
+    lab1 : for i in 0 to 1 loop
+      lab2 : for j in 0 to 1 loop
+      end loop;
+    end loop;
+  end process;
+end architecture;
+",
+    );
+}
+
+#[test]
+fn homographs_are_categorized_as_naming() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant a1 : natural := 0;
+  constant a1 : natural := 0;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].category, Some("naming"));
+}
+
+#[test]
+fn filter_diagnostics_by_duplicate_declaration_code() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant a1 : natural := 0;
+  constant a1 : natural := 0;
+  constant b1 : missing_t := 0;
+  constant c1 : natural := 0;
+  constant c1 : natural := 0;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(
+        messages_with_code(&diagnostics, "duplicate-declaration"),
+        vec![&duplicate(&code, "a1", 1, 2), &duplicate(&code, "c1", 1, 2)]
+    );
+    assert_eq!(
+        codes_present(&diagnostics),
+        vec!["duplicate-declaration"].into_iter().collect()
+    );
+}
+
+#[test]
+fn find_homographs_lists_conflicting_pairs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant a1 : natural := 0;
+  constant b1 : natural := 0;
+  constant a1 : natural := 0;
+  constant a1 : natural := 0;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(
+        find_homographs(&diagnostics),
+        vec![
+            (
+                "a1".to_owned(),
+                code.s("a1", 1).pos(),
+                code.s("a1", 2).pos()
+            ),
+            (
+                "a1".to_owned(),
+                code.s("a1", 1).pos(),
+                code.s("a1", 3).pos()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn homographs_are_distinguishable_from_syntax_errors() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant a1 : natural := 0;
+  constant a1 : natural := 0;
+end package;
+",
+    );
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(!diagnostics[0].is_syntax());
+
+    let source = crate::data::Source::inline(
+        std::path::Path::new("file.vhd"),
+        "
+package pkg is
+  constant a1 : natural :=;
+end package;
+",
+    );
+    let mut diagnostics = Vec::new();
+    crate::syntax::VHDLParser::default().parse_design_source(&source, &mut diagnostics);
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(Diagnostic::is_syntax));
+}
+
+#[test]
+fn semantic_errors_found_by_the_parser_are_not_syntax_errors() {
+    let source = crate::data::Source::inline(
+        std::path::Path::new("file.vhd"),
+        "
+package pkg is
+  attribute attr of foo, foo : signal is 0;
+  entity ent is
+  end entity;
+end package;
+
+package body pkg is
+  signal sig : bit;
+end package body;
+
+architecture a of ent2 is
+begin
+  sig <= reject 1 ns sig;
+end architecture;
+",
+    );
+    let mut diagnostics = Vec::new();
+    crate::syntax::VHDLParser::default().parse_design_source(&source, &mut diagnostics);
+    let messages: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.is_syntax()))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            ("Duplicate entity 'foo' in attribute specification", false),
+            (
+                "Entity/architecture cannot be declared inside a package",
+                false
+            ),
+            ("Declaration not allowed in package body", false),
+            ("'reject' requires 'inertial' delay", false),
+            // The parser does not recover from the misplaced 'reject'
+            (
+                "Expected 'library', 'use', 'context', 'entity', 'architecture', 'configuration' or 'package'",
+                true
+            ),
+        ]
+    );
+}
+
+#[test]
+fn forbid_homographs_of_subprogram_instances() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure gen_proc(arg : natural);
+  procedure inst is new gen_proc;
+  procedure inst is new gen_proc;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("inst", 2),
+            "Duplicate declaration of 'inst' with signature [INTEGER]",
+        )
+        .related(code.s("inst", 1), "Previously defined here")
+        .with_category("naming")
+        .with_code("duplicate-declaration")],
+    );
+}
+
+#[test]
+fn error_on_instance_of_missing_subprogram() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure inst is new missing_proc;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![missing(&code, "missing_proc", 1)]);
+}
+
+#[test]
+fn error_on_missing_name_in_generic_map_of_subprogram_instance() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure gen_proc(arg : natural);
+  procedure inst is new gen_proc generic map (width => missing_width);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![missing(&code, "missing_width", 1)]);
+}
+
+#[test]
+fn canonical_string_of_homograph_is_stable() {
+    let canonical = || {
+        let mut builder = LibraryBuilder::new();
+        let code = builder.code(
+            "libname",
+            "
+package pkg is
+constant a : natural := 0;
+constant a : natural := 0;
+end package;
+",
+        );
+
+        builder
+            .analyze()
+            .iter()
+            .map(|diagnostic| diagnostic.canonical_string(code.source()))
+            .collect::<Vec<_>>()
+    };
+
+    let expected = vec![
+        "duplicate-declaration error 4:10-4:11 Duplicate declaration of 'a' \
+         [related 3:10-3:11 Previously defined here]"
+            .to_owned(),
+    ];
+    assert_eq!(canonical(), expected);
+    assert_eq!(canonical(), expected);
+}
+
+#[test]
+fn region_add_reports_duplicate_but_nested_region_may_shadow() {
+    let code = Code::new("a b a a b");
+
+    let mut diagnostics = Vec::new();
+    let mut region = Region::default();
+    region.add(
+        code.s("a", 1).ident(),
+        NamedEntityKind::Label,
+        &mut diagnostics,
+    );
+    region.add(
+        code.s("b", 1).ident(),
+        NamedEntityKind::Label,
+        &mut diagnostics,
+    );
+    region.add(
+        code.s("a", 2).ident(),
+        NamedEntityKind::Label,
+        &mut diagnostics,
+    );
+    check_diagnostics(diagnostics, vec![duplicate(&code, "a", 1, 2)]);
+
+    let mut diagnostics = Vec::new();
+    let mut nested = region.nested();
+    nested.add(
+        code.s("a", 3).ident(),
+        NamedEntityKind::Label,
+        &mut diagnostics,
+    );
+    check_no_diagnostics(&diagnostics);
+
+    // Names of the parent region are visible within the nested region
+    let designator = code.s("b", 2).designator();
+    assert!(nested
+        .lookup_within(&designator.pos, &designator.item)
+        .is_ok());
+}
+
+#[test]
+fn concurrent_labels_are_homographs_of_entity_ports_and_generics() {
+    let mut builder = LibraryBuilder::new();
+    let ent = builder.code(
+        "libname",
+        "
+entity top is
+  generic (width : natural);
+  port (clk : in bit);
+end entity;",
+    );
+
+    let arch = builder.code(
+        "libname",
+        "
+architecture rtl of top is
+begin
+  clk : process
+  begin
+    wait;
+  end process;
+
+  width : block
+  begin
+  end block;
+
+  gen : for i in 0 to 1 generate
+  end generate;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        duplicate_in_two_files(&ent, &arch, &["clk", "width"]),
+    );
+}
+
+#[test]
+fn architecture_declarations_are_homographs_of_entity_ports_and_generics() {
+    let mut builder = LibraryBuilder::new();
+    let ent = builder.code(
+        "libname",
+        "
+entity top is
+  generic (width : natural);
+  port (clk : in bit);
+end entity;",
+    );
+
+    let arch = builder.code(
+        "libname",
+        "
+architecture rtl of top is
+  signal clk : bit;
+  constant width : natural := 0;
+  signal rst : bit;
+begin
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        duplicate_in_two_files(&ent, &arch, &["clk", "width"]),
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json_of_homograph_has_one_based_positions() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant a : natural := 0;
+  constant a : natural := 0;
+end package;
+",
+    );
+
+    let json = crate::data::diagnostics_to_json(&builder.analyze());
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let diagnostic = &value[0];
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["code"], "duplicate-declaration");
+    assert_eq!(diagnostic["message"], "Duplicate declaration of 'a'");
+    assert_eq!(diagnostic["pos"]["line"], 4);
+    assert_eq!(diagnostic["pos"]["column"], 12);
+    assert_eq!(diagnostic["pos"]["end_column"], 13);
+    assert_eq!(diagnostic["related"][0]["pos"]["line"], 3);
+    assert_eq!(diagnostic["related"][0]["pos"]["column"], 12);
+    assert_eq!(
+        diagnostic["related"][0]["message"],
+        "Previously defined here"
     );
 }
@@ -0,0 +1,956 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{Position, SrcPos, TextEdit};
+
+fn check_lints(builder: LibraryBuilder, expected: Vec<Diagnostic>) {
+    let mut builder = builder;
+    // Lints are opt-in
+    check_no_diagnostics(&builder.analyze());
+    builder.enable_lints();
+    check_diagnostics(builder.analyze(), expected);
+}
+
+#[test]
+fn no_warning_for_instantiated_component() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp1 is
+  end component;
+
+  component comp2 is
+  end component;
+begin
+  inst1 : component comp1;
+
+  gen: for i in 0 to 1 generate
+    inst2 : component comp2;
+  end generate;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_component_never_instantiated() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp1 is
+  end component;
+begin
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s1("comp1"),
+            "Component 'comp1' is declared but never instantiated",
+        )],
+    );
+}
+
+#[test]
+fn strict_mode_only_reports_lrm_errors() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp1 is
+  end component;
+
+  signal sig1 : bit;
+  signal sig1 : bit;
+begin
+end architecture;
+",
+    );
+    builder.enable_lints();
+    builder.enable_strict_mode();
+    check_diagnostics(builder.analyze(), duplicates(&code, &["sig1"]));
+}
+
+#[test]
+fn no_warning_for_protected_type_with_method() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+    procedure proc;
+  end protected;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+    procedure proc is
+    begin
+    end procedure;
+  end protected body;
+end package body;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_protected_type_without_methods() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type prot_t is protected
+  end protected;
+end package;
+
+package body pkg is
+  type prot_t is protected body
+  end protected body;
+end package body;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s1("prot_t"),
+            "Protected type 'prot_t' declares no methods",
+        )],
+    );
+}
+
+#[test]
+fn no_hint_for_branch_with_statements() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : boolean;
+begin
+  main : process
+  begin
+    if sig then
+      report \"true\";
+    else
+      null;
+    end if;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn hint_for_branch_with_only_null() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : boolean;
+
+  procedure proc(arg : boolean) is
+  begin
+    for i in 0 to 1 loop
+      if arg then
+        null;
+      end if;
+    end loop;
+  end procedure;
+begin
+  main : process
+  begin
+    if sig then
+      report \"true\";
+    elsif not sig then
+      null;
+    end if;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![
+            Diagnostic::hint(code.s("arg", 2), "Empty branch contains only 'null'"),
+            Diagnostic::hint(code.s1("not sig"), "Empty branch contains only 'null'"),
+        ],
+    );
+}
+
+#[test]
+fn no_warning_for_driven_output_ports() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity child is
+  port (o : out natural);
+end entity;
+
+architecture a of child is
+begin
+  o <= 0;
+end architecture;
+
+entity ent is
+  port (
+    input : in natural;
+    direct : out natural;
+    from_process : out natural;
+    from_instance : out natural);
+end entity;
+
+architecture a of ent is
+begin
+  direct <= input;
+
+  main : process
+  begin
+    if input = 0 then
+      from_process <= 1;
+    end if;
+    wait;
+  end process;
+
+  inst : entity work.child
+    port map (o => from_instance);
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_undriven_output_port() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    driven : out natural;
+    undriven : out natural);
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+architecture b of ent is
+begin
+  driven <= 0;
+end architecture;
+
+entity no_architecture is
+  port (o : out natural);
+end entity;
+",
+    );
+    check_lints(
+        builder,
+        vec![
+            Diagnostic::warning(
+                code.s1("no_architecture"),
+                "Entity 'no_architecture' has no architecture",
+            ),
+            Diagnostic::warning(
+                code.s1("undriven"),
+                "Output port 'undriven' is never driven in any architecture",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn warning_for_entity_without_architecture() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity orphan is
+end entity;
+
+entity ent is
+end entity;
+
+architecture rtl of ENT is
+begin
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s1("orphan"),
+            "Entity 'orphan' has no architecture",
+        )],
+    );
+}
+
+#[test]
+fn no_warning_for_shared_variable_written_by_single_process() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type prot_t is protected
+    procedure proc;
+  end protected;
+
+  type prot_t is protected body
+    procedure proc is
+    begin
+    end procedure;
+  end protected body;
+
+  shared variable counter : natural;
+  shared variable prot : prot_t;
+  signal sig : natural;
+begin
+  writer : process
+  begin
+    counter := counter + 1;
+    prot.proc;
+    wait;
+  end process;
+
+  reader : process
+  begin
+    sig <= counter;
+    prot.proc;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_shared_variable_written_by_multiple_processes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  shared variable counter : natural;
+begin
+  first : process
+  begin
+    counter := 0;
+    wait;
+  end process;
+
+  second : process
+  begin
+    if counter = 0 then
+      counter := 1;
+    end if;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s1("counter"),
+            "Shared variable 'counter' accessed by multiple processes",
+        )],
+    );
+}
+
+#[test]
+fn no_warning_for_while_loop_with_dynamic_condition() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : boolean;
+begin
+  main : process
+  begin
+    while sig loop
+      wait for 1 ns;
+    end loop;
+
+    outer : while true loop
+      while not false loop
+        exit outer when sig;
+      end loop;
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_while_loop_with_static_condition() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  main : process
+  begin
+    while false loop
+      report \"never\";
+    end loop;
+
+    while true loop
+      for i in 0 to 1 loop
+        exit;
+      end loop;
+      wait for 1 ns;
+    end loop;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![
+            Diagnostic::warning(
+                code.s1("false"),
+                "Loop condition is always false; body never executes",
+            ),
+            Diagnostic::warning(code.s1("true"), "Loop never terminates"),
+        ],
+    );
+}
+
+#[test]
+fn no_warning_for_case_covering_all_values() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done, failed);
+  signal state : state_t;
+  subtype small_t is integer range 0 to 7;
+  signal small : small_t;
+begin
+  main : process
+    variable big : integer;
+  begin
+    case state is
+      when idle => null;
+      when busy | done => null;
+      when failed => null;
+    end case;
+
+    case small is
+      when 0 => null;
+      when 1 to 6 => null;
+      when 7 => null;
+    end case;
+
+    case big is
+      when 0 => null;
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn no_warning_for_case_of_type_declared_outside_of_unit() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy);
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  port (mode : in boolean);
+end entity;
+
+architecture a of ent is
+  signal state : state_t;
+  signal flag : boolean;
+begin
+  main : process
+  begin
+    case flag is
+      when true => null;
+      when false => null;
+    end case;
+
+    case mode is
+      when true => null;
+      when false => null;
+    end case;
+
+    case state is
+      when idle => null;
+      when busy => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_case_which_may_not_cover_all_values() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  signal state : state_t;
+  signal big : integer range 0 to 1023;
+begin
+  main : process
+  begin
+    case state is
+      when idle | busy => null;
+    end case;
+
+    case big is
+      when 0 to 511 => null;
+      when 512 to 1023 => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![
+            Diagnostic::warning(
+                code.s1("case state").s1("state"),
+                "Case may not cover all values; consider 'others'",
+            )
+            .with_fixit(
+                insertion_before(&code.s("end case", 1)),
+                "  when others => null;\n    ",
+            ),
+            Diagnostic::warning(
+                code.s1("case big").s1("big"),
+                "Case may not cover all values; consider 'others'",
+            )
+            .with_fixit(
+                insertion_before(&code.s("end case", 2)),
+                "  when others => null;\n    ",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn warning_for_case_of_standard_type_which_may_not_cover_all_values() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal flag : boolean;
+begin
+  main : process
+    variable int : integer;
+    variable nat : natural;
+  begin
+    case flag is
+      when true => null;
+    end case;
+
+    case int is
+      when 0 => null;
+    end case;
+
+    case nat is
+      when 0 => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        ["flag", "int", "nat"]
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                Diagnostic::warning(
+                    code.s1(&format!("case {}", name)).s1(name),
+                    "Case may not cover all values; consider 'others'",
+                )
+                .with_fixit(
+                    insertion_before(&code.s("end case", idx + 1)),
+                    "  when others => null;\n    ",
+                )
+            })
+            .collect(),
+    );
+}
+
+#[test]
+fn warning_for_case_of_type_with_too_many_values_to_count() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type huge_t is range -9223372036854775807 to 9223372036854775807;
+  signal huge : huge_t;
+begin
+  main : process
+  begin
+    case huge is
+      when 0 => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s1("case huge").s1("huge"),
+            "Case may not cover all values; consider 'others'",
+        )
+        .with_fixit(
+            insertion_before(&code.s1("end case")),
+            "  when others => null;\n    ",
+        )],
+    );
+}
+
+/// An empty position at the start of the code
+fn insertion_before(code: &Code) -> SrcPos {
+    let pos = code.pos();
+    pos.source.pos(pos.start(), pos.start())
+}
+
+#[test]
+fn missing_others_fixit_inserts_alternative_before_end_case() {
+    let mut builder = LibraryBuilder::new();
+    builder.enable_lints();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy, done);
+  signal state : state_t;
+begin
+  main : process
+  begin
+    case state is
+      when idle => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].fixits(),
+        [TextEdit {
+            pos: code
+                .source()
+                .pos(Position::new(12, 4), Position::new(12, 4)),
+            new_text: "  when others => null;\n    ".to_owned(),
+        }]
+    );
+    assert!(diagnostics[0]
+        .canonical_string(code.source())
+        .ends_with(" [fixit 13:5-13:5 '  when others => null;\\n    ']"));
+}
+
+#[test]
+fn no_hint_for_distinctly_named_parameter() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  function len(value : natural) return natural;
+end package;
+
+package body pkg is
+  function len(value : natural) return natural is
+  begin
+    if value = 0 then
+      return 0;
+    end if;
+    return len(value - 1);
+  end function;
+end package body;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn hint_for_parameter_shadowing_subprogram_name() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  procedure proc(proc : natural);
+end package;
+
+package body pkg is
+  function len(len : natural) return natural is
+  begin
+    return len;
+  end function;
+end package body;
+",
+    );
+    check_lints(
+        builder,
+        vec![
+            Diagnostic::hint(
+                code.s("proc", 3),
+                "Parameter 'proc' shadows enclosing subprogram name",
+            ),
+            Diagnostic::hint(
+                code.s("len", 2),
+                "Parameter 'len' shadows enclosing subprogram name",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn no_warning_for_concurrent_procedure_call_waiting_for_signal() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk : boolean;
+
+  procedure wait_clk(signal clk : in boolean) is
+  begin
+    wait until clk;
+    wait for 1 ns;
+  end procedure;
+begin
+  wait_clk(clk);
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn warning_for_concurrent_procedure_call_waiting_for_static_condition() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  procedure stall;
+
+  procedure stall is
+  begin
+    report \"stalled\";
+    wait until true;
+  end procedure;
+begin
+  stall;
+end architecture;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::warning(
+            code.s("stall", 4),
+            "Concurrent call to procedure 'stall' may loop forever",
+        )
+        .related(code.s1("true"), "Wait with static condition")],
+    );
+}
+
+#[test]
+fn hint_for_unconditional_recursion() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    return n * fact(n - 1);
+  end function;
+end package body;
+",
+    );
+    check_lints(
+        builder,
+        vec![Diagnostic::hint(
+            code.s1("fact(n - 1)").s1("fact"),
+            "Subprogram 'fact' calls itself without a terminating condition",
+        )],
+    );
+}
+
+#[test]
+fn no_hint_for_guarded_or_missing_recursion() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+package body pkg is
+  function fact(n : natural) return natural is
+  begin
+    if n = 0 then
+      return 1;
+    end if;
+    return n;
+  end function;
+
+  function fact2(n : natural) return natural is
+  begin
+    if n = 0 then
+      return 1;
+    else
+      return n * fact2(n - 1);
+    end if;
+  end function;
+end package body;
+",
+    );
+    check_lints(builder, vec![]);
+}
+
+#[test]
+fn no_diagnostics_for_portless_testbench() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity dut is
+  generic (width : natural := 8);
+  port (
+    clk : in bit;
+    q : out bit_vector(width - 1 downto 0));
+end entity;
+
+architecture rtl of dut is
+begin
+  process (clk)
+  begin
+    if clk = '1' then
+      q <= (others => '0');
+    end if;
+  end process;
+end architecture;
+
+entity tb is
+end entity;
+
+architecture sim of tb is
+  signal clk : bit := '0';
+  signal q : bit_vector(7 downto 0);
+begin
+  clk <= not clk after 5 ns;
+
+  dut_inst : entity work.dut
+    generic map (width => 8)
+    port map (clk => clk, q => q);
+end architecture;
+",
+    );
+    check_lints(builder, vec![]);
+}
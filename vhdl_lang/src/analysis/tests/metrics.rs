@@ -0,0 +1,53 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::ast::{PrimaryKind, SecondaryKind, UnitId};
+
+#[test]
+fn metrics_contain_timing_of_each_unit_when_enabled() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+package pkg is
+end package;
+",
+    );
+    builder.enable_lints();
+    let (mut root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    // Metrics are opt-in
+    assert!(root.metrics().units.is_empty());
+
+    root.enable_metrics(true);
+    let metrics = root.metrics();
+    let lib = root.symbol_utf8("libname");
+    let sym = |name| root.symbol_utf8(name);
+    let unit_ids: Vec<_> = metrics
+        .units
+        .iter()
+        .map(|unit| unit.unit_id.clone())
+        .filter(|unit_id| unit_id.library_name() == &lib)
+        .collect();
+
+    assert_eq!(
+        unit_ids,
+        vec![
+            UnitId::primary(&lib, PrimaryKind::Entity, &sym("ent")),
+            UnitId::secondary(&lib, SecondaryKind::Architecture, &sym("ent"), &sym("a")),
+            UnitId::package(&lib, &sym("pkg")),
+        ]
+    );
+}
@@ -7,15 +7,20 @@
 mod circular_dependencies;
 mod context_clause;
 mod deferred_constant;
+mod diagnostic_callback;
+mod file_declaration;
 mod homographs;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod lint;
+mod metrics;
 mod package_instance;
 mod protected_type;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
+mod sensitivity_list;
 mod util;
 mod visibility;
 
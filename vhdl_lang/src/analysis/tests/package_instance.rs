@@ -67,3 +67,42 @@ end package;
         ],
     );
 }
+
+#[test]
+fn package_instance_generic_map_must_match_generics() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package gpkg is
+generic (width : natural; depth : natural := 4);
+end package;
+
+package ipkg_ok is new work.gpkg generic map (width => 8);
+package ipkg_missing is new work.gpkg generic map (depth => 8);
+package ipkg_unknown is new work.gpkg generic map (width => 8, height => 2);
+
+package nested is
+package ipkg_many is new work.gpkg generic map (8, 4, 2);
+end package;
+        ",
+    );
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("work.gpkg", 2),
+                "Missing association for generic 'width'",
+            ),
+            Diagnostic::error(
+                code.s1("height"),
+                "No generic 'height' within package 'gpkg'",
+            ),
+            Diagnostic::error(
+                code.s1("8, 4, 2").s1("2"),
+                "Too many generic associations, package 'gpkg' has 2",
+            ),
+        ],
+    );
+}
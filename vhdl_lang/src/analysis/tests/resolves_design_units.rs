@@ -115,6 +115,37 @@ end configuration;
     );
 }
 
+#[test]
+fn resolves_use_clauses_in_configuration_declarative_part() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+end package;
+
+entity ent is
+end entity;
+
+configuration cfg of ent is
+use work.pkg.all;
+use work.pkg.all;
+use work.missing.all;
+for rtl
+end for;
+end configuration;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s1("missing"),
+            "No primary unit 'missing' within library 'libname'",
+        )],
+    );
+}
+
 #[test]
 fn search_reference_from_configuration_to_entity() {
     check_search_reference(
@@ -201,6 +232,54 @@ end package body;
     );
 }
 
+#[test]
+fn error_on_body_of_package_in_other_library() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib2",
+        "
+package pkg is
+end package;",
+    );
+    let code = builder.code(
+        "libname",
+        "
+library lib2;
+
+package body pkg is
+end package body;
+",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::error(
+            code.s("pkg", 1),
+            "No package 'pkg' within library 'libname'",
+        )],
+    );
+}
+
+#[test]
+fn no_error_on_body_of_package_in_same_library() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+end package;",
+    );
+    builder.code(
+        "libname",
+        "
+package body pkg is
+end package body;
+",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
 #[test]
 fn error_on_package_body_before_package_in_same_file() {
     let mut builder = LibraryBuilder::new();
@@ -502,3 +581,179 @@ end architecture;
         )],
     );
 }
+
+#[test]
+fn entity_instantiation_with_complete_associations() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+  generic (
+    width : natural;
+    depth : natural := 1);
+  port (
+    clk : in bit;
+    rst : in bit := '0';
+    dout : out bit);
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, dout : bit;
+begin
+  named_inst : entity work.ent
+    generic map (width => 8)
+    port map (clk => clk, dout => open);
+
+  positional_inst : entity work.ent(a)
+    generic map (8, 2)
+    port map (clk, '1', dout);
+end architecture;
+",
+    );
+}
+
+#[test]
+fn entity_instantiation_with_missing_and_extra_associations() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    width : natural);
+  port (
+    clk : in bit;
+    dout : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal clk, dout : bit;
+begin
+  inst1 : entity work.ent
+    port map (dout => dout);
+
+  inst2 : entity work.ent
+    generic map (width => 8, height => 2)
+    port map (clk, dout, clk);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("work.ent", 1),
+                "Missing association for generic 'width'",
+            ),
+            Diagnostic::error(code.s("work.ent", 1), "Missing association for port 'clk'"),
+            Diagnostic::error(code.s1("height"), "No generic 'height' within entity 'ent'"),
+            Diagnostic::error(
+                code.s("clk", 4),
+                "Too many port associations, entity 'ent' has 2",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn entity_instantiation_with_distinct_output_actuals() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    din : in bit;
+    dout1 : out bit;
+    dout2 : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal s1, s2, s3 : bit;
+begin
+  inst : entity work.ent
+    port map (din => s1, dout1 => s2, dout2 => s3);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn entity_instantiation_with_same_actual_on_multiple_inouts() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    bus1 : inout bit;
+    bus2 : inout bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal s : bit;
+begin
+  inst : entity work.ent
+    port map (s, s);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn entity_instantiation_with_same_actual_on_multiple_outputs() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    din : in bit;
+    dout1 : out bit;
+    dout2 : out bit);
+end entity;
+
+entity top is
+end entity;
+
+architecture a of top is
+  signal s1, s2 : bit;
+begin
+  inst : entity work.ent
+    port map (s1, s2, dout2 => s2);
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s("s2", 3),
+            "Signal 's2' drives multiple output ports of instance 'inst'",
+        )],
+    );
+}
@@ -5,6 +5,10 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::analysis::ResolutionStrictness;
+use crate::ast::search::NameReference;
+use crate::ast::Designator;
+use crate::data::SrcPos;
 
 #[test]
 fn resolves_names_in_object_decl_init_expressions() {
@@ -1107,6 +1111,51 @@ end package body;
     );
 }
 
+#[test]
+fn object_alias_with_compatible_subtype() {
+    check_code_with_no_diagnostics(
+        "
+package pkg is
+  constant c_int : integer := 0;
+  constant c_vec : bit_vector(0 to 3) := \"0000\";
+  alias a_int : natural is c_int;
+  alias a_vec : bit_vector(0 to 3) is c_vec;
+end package;
+",
+    );
+}
+
+#[test]
+fn error_on_object_alias_with_incompatible_subtype() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c_int : integer := 0;
+  constant c_vec : bit_vector(0 to 3) := \"0000\";
+  alias a_int : bit_vector is c_int;
+  alias a_vec : natural is c_vec;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("bit_vector", 2),
+                "Alias subtype does not match aliased object 'c_int'",
+            ),
+            Diagnostic::error(
+                code.s1("natural"),
+                "Alias subtype does not match aliased object 'c_vec'",
+            ),
+        ],
+    );
+}
+
 #[test]
 fn signatures_are_compared_with_base_type() {
     check_code_with_no_diagnostics(
@@ -1170,3 +1219,908 @@ end package body;
         Some(code.s("subpgm", 1).pos())
     );
 }
+
+#[test]
+fn can_select_from_record_object() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : natural;
+  end record;
+  signal rec : rec_t;
+  signal dst : natural;
+begin
+  dst <= rec.field;
+end architecture;
+",
+    );
+}
+
+#[test]
+fn cannot_select_from_scalar_object() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal int_sig : integer;
+  signal nat_sig : natural;
+  signal dst : natural;
+begin
+  dst <= int_sig.field;
+  dst <= nat_sig.other_field;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("field"), "Cannot select 'field' from 'int_sig'"),
+            Diagnostic::error(
+                code.s1("other_field"),
+                "Cannot select 'other_field' from 'nat_sig'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn assignment_class_matches_target() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+  port (output : out natural);
+end entity;
+
+architecture a of ent is
+  signal sig : natural;
+begin
+  main : process
+    variable var : natural;
+  begin
+    var := 0;
+    sig <= var;
+    output <= sig;
+    wait;
+  end process;
+end architecture;
+",
+    );
+}
+
+#[test]
+fn assignment_class_mismatch() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (output : out natural);
+end entity;
+
+architecture a of ent is
+  signal sig : natural;
+begin
+  main : process
+    variable var : natural;
+  begin
+    sig := 0;
+    output := 0;
+    var <= 0;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("sig", 3), "Use '<=' to assign signal 'sig'"),
+            Diagnostic::error(code.s("output", 2), "Use '<=' to assign signal 'output'"),
+            Diagnostic::error(code.s("var", 3), "Use ':=' to assign variable 'var'"),
+        ],
+    );
+}
+
+#[test]
+fn linkage_port_may_only_be_associated_as_a_whole() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity sub is
+  port (io : linkage bit);
+end entity;
+
+architecture a of sub is
+begin
+end architecture;
+
+entity ent is
+  port (io : linkage bit);
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  inst : entity work.sub port map (io => io);
+  io <= '1';
+  sig <= io;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("io", 5),
+                "Port 'io' of mode linkage may only be associated as a whole",
+            )
+            .related(code.s("io", 2), "Declared here"),
+            Diagnostic::error(
+                code.s("io", 6),
+                "Port 'io' of mode linkage may only be associated as a whole",
+            )
+            .related(code.s("io", 2), "Declared here"),
+        ],
+    );
+}
+
+#[test]
+fn error_on_type_and_object_confusion() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  subtype byte_t is natural range 0 to 255;
+  signal count : byte_t;
+  subtype copy_t is count;
+  signal other : count;
+begin
+  byte_t <= 0;
+
+  main : process
+  begin
+    byte_t := 0;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("count", 2), "'count' is an object, not a type")
+                .related(code.s("count", 1), "Defined here"),
+            Diagnostic::error(code.s("count", 3), "'count' is an object, not a type")
+                .related(code.s("count", 1), "Defined here"),
+            Diagnostic::error(code.s("byte_t", 3), "'byte_t' is a type, not an object"),
+            Diagnostic::error(code.s("byte_t", 4), "'byte_t' is a type, not an object"),
+        ],
+    );
+}
+
+#[test]
+fn group_declaration_must_reference_group_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  signal s1, s2 : bit;
+  group pin2pin is (signal, signal);
+  group ok : pin2pin (s1, s2);
+  group bad : s1 (s1, s2);
+  group missing : missing_t (s1, s2);
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s("s1", 3), "Expected group template, got signal 's1'")
+                .related(code.s("s1", 1), "Defined here"),
+            missing(&code, "missing_t", 1),
+        ],
+    );
+}
+
+#[test]
+fn error_on_null_aggregate_for_non_null_target() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant good : bit_vector(0 to 3) := (0 to 3 => '0');
+  constant null_ok : bit_vector(1 to 0) := (1 to 0 => '0');
+  constant bad : bit_vector(0 to 3) := (3 downto 4 => '1');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("(3 downto 4 => '1')"),
+            "Aggregate produces a null array but target is non-null",
+        )],
+    );
+}
+
+#[test]
+fn package_signal_may_be_read_in_package_body() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  signal s : bit;
+  procedure proc(signal dst : out bit);
+end package;
+
+package body pkg is
+  procedure proc(signal dst : out bit) is
+  begin
+    dst <= s;
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_package_signal_assigned_in_package_body() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  signal s : bit;
+  procedure proc;
+end package;
+
+package body pkg is
+  procedure proc is
+  begin
+    s <= '1';
+  end procedure;
+end package body;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("s <= '1'").s1("s"),
+            "Cannot assign package signal 's' in package body",
+        )],
+    );
+}
+
+#[test]
+fn error_on_assignment_to_part_of_constant() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : bit;
+  end record;
+  constant c_vec : bit_vector(0 to 1) := \"00\";
+  constant c_rec : rec_t := (field => '0');
+  signal s_vec : bit_vector(0 to 1);
+begin
+  main : process
+  begin
+    c_vec(0) <= '1';
+    c_vec(0 to 1) <= \"11\";
+    c_rec.field <= '1';
+    s_vec(0) <= '1';
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("c_vec(0)"),
+                "Cannot assign to part of constant 'c_vec'",
+            ),
+            Diagnostic::error(
+                code.s1("c_vec(0 to 1)"),
+                "Cannot assign to part of constant 'c_vec'",
+            ),
+            Diagnostic::error(
+                code.s1("c_rec.field"),
+                "Cannot assign to part of constant 'c_rec'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn error_on_assignment_to_constant() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : bit := '0';
+begin
+  main : process
+  begin
+    c <= '1';
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("c <= '1'").s1("c"),
+            "Cannot assign to constant 'c'",
+        )],
+    );
+}
+
+#[test]
+fn physical_literal_with_visible_unit() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant delay : time := 10 ns;
+
+  type distance_t is range 0 to 1000
+    units
+      mm;
+      m = 1000 mm;
+    end units;
+
+  constant len : distance_t := 2 m;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_physical_literal_with_unknown_unit() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : natural := 0;
+  constant delay : time := 10 widgets;
+  constant other : time := 5 c0;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(code.s1("10 widgets"), "Unknown physical unit 'widgets'"),
+            Diagnostic::error(code.s1("5 c0"), "Unknown physical unit 'c0'"),
+        ],
+    );
+}
+
+#[test]
+fn selected_assignment_with_matching_choice_types() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sel : boolean;
+  signal num : natural;
+  signal dest : natural;
+begin
+  with sel select
+    dest <= 1 when true,
+            0 when false;
+
+  with num select
+    dest <= 1 when 0,
+            0 when others;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_selected_assignment_with_mismatched_choice_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sel : boolean;
+  signal dest : natural;
+begin
+  with sel select
+    dest <= 1 when 1,
+            0 when others;
+
+  process
+    variable v : natural;
+  begin
+    with sel select
+      v := 1 when true,
+           0 when 0;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("when 1").s1("1"),
+                "Choice type does not match selector in selected assignment",
+            ),
+            Diagnostic::error(
+                code.s1("when 0").s1("0"),
+                "Choice type does not match selector in selected assignment",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn case_expression_of_discrete_type() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process
+    variable num : natural;
+    variable flag : boolean;
+    variable str : string(1 to 2);
+  begin
+    case num is
+      when others => null;
+    end case;
+    case flag is
+      when others => null;
+    end case;
+    case str is
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_case_expression_of_non_discrete_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : natural;
+  end record;
+  type float_t is range -1.0 to 1.0;
+begin
+  process
+    variable rl : real;
+    variable rec : rec_t;
+    variable flt : float_t;
+    variable dly : time;
+  begin
+    case rl is
+      when others => null;
+    end case;
+    case rec is
+      when others => null;
+    end case;
+    case flt is
+      when others => null;
+    end case;
+    case dly is
+      when others => null;
+    end case;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        ["case rl", "case rec", "case flt", "case dly"]
+            .iter()
+            .map(|case| {
+                Diagnostic::error(
+                    code.s1(case).s1(&case[5..]),
+                    "Case expression must be of a discrete type",
+                )
+            })
+            .collect(),
+    );
+}
+
+#[test]
+fn attribute_specification_of_matching_entity_class() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  attribute attr : string;
+  signal sig : bit;
+  constant const : natural := 0;
+  subtype sub_t is natural range 0 to 1;
+  component comp is
+  end component;
+  function fun return natural;
+  procedure proc;
+  attribute attr of sig : signal is \"value\";
+  attribute attr of const : constant is \"value\";
+  attribute attr of sub_t : subtype is \"value\";
+  attribute attr of comp : component is \"value\";
+  attribute attr of fun : function is \"value\";
+  attribute attr of proc : procedure is \"value\";
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_attribute_specification_of_other_entity_class() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  attribute attr : string;
+  constant const : natural := 0;
+  function fun return natural;
+  attribute attr of const : signal is \"value\";
+  attribute attr of fun : procedure is \"value\";
+begin
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("of const").s1("const"),
+                "Entity 'const' is not of class 'signal'",
+            ),
+            Diagnostic::error(
+                code.s1("of fun").s1("fun"),
+                "Entity 'fun' is not of class 'procedure'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn no_overflow_for_null_aggregate_check_of_extreme_bounds() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : bit_vector(-9223372036854775808 to 0) := (0 to 1 => '0');
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn external_references_lists_unresolved_library_names() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    field : natural;
+  end record;
+  signal rec : rec_t;
+  signal sig : natural;
+begin
+  sig <= work.other_pkg.thing;
+  sig <= rec.field;
+end architecture;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("other_pkg"),
+            "No primary unit 'other_pkg' within library 'libname'",
+        )],
+    );
+    assert_eq!(
+        root.external_references(code.source(), code.s1("a of ent").start()),
+        vec![(
+            code.symbol("other_pkg"),
+            code.s1("work.other_pkg.thing").pos()
+        )]
+    );
+}
+
+#[test]
+fn references_lists_names_with_their_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity top is
+end entity;
+
+architecture rtl of top is
+  signal sig0 : bit;
+  signal sig1 : bit;
+begin
+  sig1 <= sig0;
+  sig0 <= missing;
+end architecture;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    let references: Vec<_> = root
+        .references(code.source(), code.s1("rtl").start())
+        .into_iter()
+        .filter(|reference| reference.designator != Designator::Identifier(code.symbol("bit")))
+        .collect();
+
+    let reference = |name: &str, occurence: usize, decl_pos: Option<SrcPos>| NameReference {
+        designator: Designator::Identifier(code.symbol(name)),
+        pos: code.s(name, occurence).pos(),
+        decl_pos,
+    };
+
+    assert_eq!(
+        references,
+        vec![
+            reference("top", 2, Some(code.s("top", 1).pos())),
+            reference("sig1", 2, Some(code.s("sig1", 1).pos())),
+            reference("sig0", 2, Some(code.s("sig0", 1).pos())),
+            reference("sig0", 3, Some(code.s("sig0", 1).pos())),
+            reference("missing", 1, None),
+        ]
+    );
+}
+
+#[test]
+fn port_constraint_may_reference_generic() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    width : natural;
+    depth : natural := width * 2);
+  port (
+    d : bit_vector(width - 1 downto 0);
+    q : bit_vector(depth - 1 downto 0));
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_generic_used_before_declaration() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (
+    depth : natural := width * 2;
+    width : natural;
+    other : natural := missing);
+  port (
+    d : bit_vector(width - 1 downto 0));
+end entity;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("width * 2").s1("width"),
+                "Generic 'width' used before declaration",
+            ),
+            Diagnostic::error(code.s1("missing"), "No declaration of 'missing'"),
+        ],
+    );
+}
+
+#[test]
+fn error_on_name_selected_from_missing_primary_unit_when_strict() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : natural := work.other.thing;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("other"),
+            "No primary unit 'other' within library 'libname'",
+        )],
+    );
+}
+
+#[test]
+fn lenient_resolution_assumes_missing_primary_unit_is_valid() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_resolution_strictness(ResolutionStrictness::Lenient);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  constant c0 : natural := work.other.thing;
+  constant c1 : natural := missing;
+end package;
+",
+    );
+
+    // Names which are not selected from a library are still reported
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![missing(&code, "missing", 1)]);
+}
+
+#[test]
+fn lenient_resolution_assumes_missing_library_and_used_primary_units_are_valid() {
+    let code = "
+library unloaded;
+use unloaded.pkg.all;
+use work.other.all;
+use work.other.thing;
+
+package pkg is
+  constant c0 : work.other.typ_t := unloaded.pkg.value;
+  signal s0 : unloaded.pkg.typ_t;
+  constant c1 : undeclared_t := 0;
+end package;
+";
+
+    let mut builder = LibraryBuilder::new();
+    let strict_code = builder.code("libname", code);
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(strict_code.s1("unloaded"), "No such library 'unloaded'"),
+            Diagnostic::error(
+                strict_code.s("other", 1),
+                "No primary unit 'other' within library 'libname'",
+            ),
+            Diagnostic::error(
+                strict_code.s("other", 2),
+                "No primary unit 'other' within library 'libname'",
+            ),
+            Diagnostic::error(
+                strict_code.s("other", 3),
+                "No primary unit 'other' within library 'libname'",
+            ),
+            missing(&strict_code, "unloaded", 2),
+            missing(&strict_code, "unloaded", 3),
+            missing(&strict_code, "unloaded", 4),
+            missing(&strict_code, "undeclared_t", 1),
+        ],
+    );
+
+    let mut builder = LibraryBuilder::new();
+    builder.set_resolution_strictness(ResolutionStrictness::Lenient);
+    let code = builder.code("libname", code);
+
+    // Names which are not selected from a library are still reported
+    let diagnostics = builder.analyze();
+    check_diagnostics(diagnostics, vec![missing(&code, "undeclared_t", 1)]);
+}
@@ -5,6 +5,7 @@
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
 use super::*;
+use crate::analysis::VHDLStandard;
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -362,7 +363,10 @@ end package body;
     let diagnostics = builder.analyze();
     check_diagnostics(
         diagnostics,
-        vec![kind_error(&code, "bad", 2, 1, "type", "constant 'bad'")],
+        vec![
+            Diagnostic::error(code.s("bad", 2), "'bad' is an object, not a type")
+                .related(code.s("bad", 1), "Defined here"),
+        ],
     );
 }
 
@@ -382,14 +386,10 @@ end package;
     let diagnostics = builder.analyze();
     check_diagnostics(
         diagnostics,
-        vec![kind_error(
-            &code,
-            "bad",
-            2,
-            1,
-            "type",
-            "alias 'bad' of constant 'const'",
-        )],
+        vec![
+            Diagnostic::error(code.s("bad", 2), "'bad' is an object, not a type")
+                .related(code.s("bad", 1), "Defined here"),
+        ],
     );
 }
 
@@ -453,3 +453,200 @@ pub fn kind_error(
     )
     .related(code.s(name, occ_decl), "Defined here")
 }
+
+#[test]
+fn resolves_type_mark_in_interface_array_type() {
+    check_missing(
+        "
+entity ent is
+  generic (
+    type arr_t is array (natural range <>) of bit;
+    type arr2_t is array (missing range <>) of missing);
+end entity;
+",
+    );
+}
+
+#[test]
+fn generate_parameter_type_from_discrete_range() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type enum_t is (alpha, beta);
+begin
+  gen_range : for i in 0 to 3 generate
+  end generate;
+
+  gen_type : for j in enum_t generate
+  end generate;
+
+  gen_subtype : for k in natural range 0 to 3 generate
+  end generate;
+end architecture;
+",
+    );
+}
+
+#[test]
+fn error_on_generate_parameter_of_unknown_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  gen : for idx in missing_t generate
+  end generate;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            missing(&code, "missing_t", 1),
+            Diagnostic::error(
+                code.s1("idx"),
+                "Cannot determine type of generate parameter 'idx'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn range_constraint_on_integer_subtype() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type int_t is range 0 to 255;
+  subtype sub_t is int_t range 0 to 15;
+  constant c0 : natural range 0 to 7 := 0;
+  constant c1 : bit_vector(0 to 3) := \"0000\";
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_index_constraint_on_scalar_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type int_t is range 0 to 255;
+  subtype sub_t is int_t(0 to 15);
+  constant c0 : natural(0 to 7) := 0;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("(0 to 15)"),
+                "Incompatible constraint for type 'int_t'",
+            ),
+            Diagnostic::error(
+                code.s1("(0 to 7)"),
+                "Incompatible constraint for type 'NATURAL'",
+            ),
+        ],
+    );
+}
+
+#[test]
+fn record_elements_with_well_formed_subtypes() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    num : natural range 0 to 7;
+    vec : bit_vector(0 to 3);
+    -- Unconstrained element subtypes are legal since VHDL-2008
+    any : bit_vector;
+  end record;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+}
+
+#[test]
+fn error_on_record_element_with_incompatible_constraint() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type rec_t is record
+    num : integer(0 to 7);
+  end record;
+end package;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::error(
+            code.s1("(0 to 7)"),
+            "Incompatible constraint for type 'INTEGER'",
+        )],
+    );
+}
+
+#[test]
+fn error_on_unconstrained_record_element_in_vhdl_1993() {
+    let code = "
+package pkg is
+  type arr_t is array (natural range <>) of bit;
+  type rec_t is record
+    vec : bit_vector(0 to 3);
+    arr : arr_t;
+    any : bit_vector;
+  end record;
+end package;
+";
+
+    let mut builder = LibraryBuilder::new();
+    builder.code("libname", code);
+    let diagnostics = builder.analyze();
+    check_no_diagnostics(&diagnostics);
+
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard(VHDLStandard::VHDL1993);
+    let code = builder.code("libname", code);
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s1("arr : arr_t").s1("arr"),
+                "Record element 'arr' must have a constrained subtype",
+            ),
+            Diagnostic::error(
+                code.s1("any"),
+                "Record element 'any' must have a constrained subtype",
+            ),
+        ],
+    );
+}
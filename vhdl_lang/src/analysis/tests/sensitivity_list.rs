@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+
+#[test]
+fn allows_signals_and_ports_in_sensitivity_list() {
+    check_code_with_no_diagnostics(
+        "
+entity ent is
+  port (clk : in bit);
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process (clk, sig)
+  begin
+  end process;
+end architecture;
+",
+    );
+}
+
+#[test]
+fn error_on_constant_in_sensitivity_list() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  generic (g0 : natural);
+end entity;
+
+architecture a of ent is
+  constant c0 : bit := '0';
+  signal sig : bit;
+begin
+  process (sig, c0, g0)
+  begin
+  end process;
+end architecture;
+",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::error(
+                code.s("c0", 2),
+                "Sensitivity list entry 'c0' is not a signal",
+            ),
+            Diagnostic::error(
+                code.s("g0", 2),
+                "Sensitivity list entry 'g0' is not a signal",
+            ),
+        ],
+    );
+}
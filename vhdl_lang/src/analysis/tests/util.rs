@@ -4,17 +4,22 @@
 //
 // Copyright (c) 2019, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
+use crate::analysis::{DesignRoot, ResolutionStrictness, VHDLStandard};
 use crate::data::*;
 use crate::syntax::test::*;
 use crate::syntax::Symbols;
 use pretty_assertions::assert_eq;
 use std::collections::{hash_map::Entry, HashMap};
+use std::path::Path;
 use std::sync::Arc;
 
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    lints: bool,
+    strict: bool,
+    strictness: ResolutionStrictness,
+    standard: VHDLStandard,
 }
 
 impl LibraryBuilder {
@@ -22,10 +27,30 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::new(),
             libraries: HashMap::default(),
+            lints: false,
+            strict: false,
+            strictness: ResolutionStrictness::Strict,
+            standard: VHDLStandard::VHDL2008,
         }
     }
 
-    fn add_code(&mut self, library_name: &str, code: Code) {
+    pub fn enable_lints(&mut self) {
+        self.lints = true;
+    }
+
+    pub fn enable_strict_mode(&mut self) {
+        self.strict = true;
+    }
+
+    pub fn set_resolution_strictness(&mut self, strictness: ResolutionStrictness) {
+        self.strictness = strictness;
+    }
+
+    pub fn set_standard(&mut self, standard: VHDLStandard) {
+        self.standard = standard;
+    }
+
+    pub fn add_code(&mut self, library_name: &str, code: Code) {
         let library_name = self.code_builder.symbol(library_name);
         match self.libraries.entry(library_name) {
             Entry::Occupied(mut entry) => {
@@ -44,8 +69,20 @@ impl LibraryBuilder {
     }
 
     pub fn get_analyzed_root(&self) -> (DesignRoot, Vec<Diagnostic>) {
-        let mut root = DesignRoot::new(self.code_builder.symbols.clone());
         let mut diagnostics = Vec::new();
+        let root = self.analyze_into(&mut diagnostics);
+        (root, diagnostics)
+    }
+
+    /// Analyze all code while pushing each diagnostic to the handler as soon as it is found
+    pub fn analyze_into(&self, diagnostics: &mut dyn DiagnosticHandler) -> DesignRoot {
+        let mut root = DesignRoot::new(self.code_builder.symbols.clone());
+        if self.strict {
+            root.enable_strict_mode();
+        }
+        root.enable_lints(self.lints);
+        root.set_resolution_strictness(self.strictness);
+        root.set_standard(self.standard);
 
         add_standard_library(self.symbols(), &mut root);
 
@@ -55,9 +92,8 @@ impl LibraryBuilder {
             }
         }
 
-        root.analyze(&mut diagnostics);
-
-        (root, diagnostics)
+        root.analyze(diagnostics);
+        root
     }
 
     pub fn take_code(self) -> Vec<(Symbol, Code)> {
@@ -115,6 +151,8 @@ pub fn duplicate(code: &Code, name: &str, occ1: usize, occ2: usize) -> Diagnosti
         format!("Duplicate declaration of '{}'", &name),
     )
     .related(code.s(&name, occ1), "Previously defined here")
+    .with_category("naming")
+    .with_code("duplicate-declaration")
 }
 
 pub fn duplicates(code: &Code, names: &[&str]) -> Vec<Diagnostic> {
@@ -133,7 +171,9 @@ pub fn duplicate_in_two_files(code1: &Code, code2: &Code, names: &[&str]) -> Vec
                 code2.s1(&name),
                 format!("Duplicate declaration of '{}'", &name),
             )
-            .related(code1.s1(&name), "Previously defined here"),
+            .related(code1.s1(&name), "Previously defined here")
+            .with_category("naming")
+            .with_code("duplicate-declaration"),
         )
     }
     diagnostics
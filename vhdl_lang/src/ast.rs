@@ -9,7 +9,11 @@
 // Track here: https://github.com/rust-lang/rust/issues/29641
 #![allow(clippy::large_enum_variant)]
 
+mod base_type;
+mod declarative_regions;
 mod display;
+mod enclosing;
+mod fingerprint;
 mod name_util;
 
 #[macro_use]
@@ -18,7 +22,10 @@ mod any_design_unit;
 #[macro_use]
 pub mod search;
 
+pub use self::base_type::*;
 pub use self::display::*;
+pub use self::enclosing::*;
+pub use self::fingerprint::*;
 pub use self::name_util::*;
 pub use any_design_unit::*;
 
@@ -340,10 +347,29 @@ pub struct ElementDeclaration {
     pub subtype: SubtypeIndication,
 }
 
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupTemplateDeclaration {
+    pub ident: Ident,
+    pub entity_classes: Vec<EntityClass>,
+    /// The last entity class is followed by <> and may be repeated
+    pub is_repeated: bool,
+}
+
+/// LRM 6.10 Group declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupDeclaration {
+    pub ident: Ident,
+    pub template_name: WithPos<SelectedName>,
+    pub constituents: Vec<WithPos<Name>>,
+}
+
 /// LRM 5.6.2 Protected type declarations
 #[derive(PartialEq, Debug, Clone)]
 pub enum ProtectedTypeDeclarativeItem {
     Subprogram(SubprogramDeclaration),
+    Use(WithPos<UseClause>),
+    AttributeSpecification(AttributeSpecification),
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
@@ -422,7 +448,11 @@ pub enum EntityClass {
     Component,
     Constant,
     Type,
+    Subtype,
     Label,
+    Units,
+    Group,
+    File,
 }
 
 /// LRM 7.2 Attribute specification
@@ -509,17 +539,24 @@ pub struct TypeDeclaration {
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum ObjectClass {
     Signal,
-    // @TODO signal_kind
     Constant,
     Variable,
     SharedVariable,
 }
 
+/// LRM 6.4.2.3 Signal declarations
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SignalKind {
+    Register,
+    Bus,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct ObjectDeclaration {
     pub class: ObjectClass,
     pub ident: Ident,
     pub subtype_indication: SubtypeIndication,
+    pub signal_kind: Option<SignalKind>,
     pub expression: Option<WithPos<Expression>>,
 }
 
@@ -561,6 +598,22 @@ pub struct SubprogramBody {
     pub statements: Vec<LabeledSequentialStatement>,
 }
 
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SubprogramKind {
+    Function,
+    Procedure,
+}
+
+/// LRM 4.4 Subprogram instantiation declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct SubprogramInstantiation {
+    pub kind: SubprogramKind,
+    pub designator: WithPos<SubprogramDesignator>,
+    pub subprogram_name: WithPos<SelectedName>,
+    pub signature: Option<WithPos<Signature>>,
+    pub generic_map: Option<Vec<AssociationElement>>,
+}
+
 /// LRM 4.5.3 Signatures
 #[derive(PartialEq, Debug, Clone)]
 pub enum Signature {
@@ -611,11 +664,19 @@ pub struct InterfacePackageDeclaration {
     pub generic_map: InterfacePackageGenericMapAspect,
 }
 
+/// LRM 6.5.3 Interface type declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct InterfaceTypeDeclaration {
+    pub ident: Ident,
+    /// VHDL-2019 incomplete type definition, only array types are supported
+    pub def: Option<TypeDefinition>,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum InterfaceDeclaration {
     Object(InterfaceObjectDeclaration),
     File(InterfaceFileDeclaration),
-    Type(Ident),
+    Type(InterfaceTypeDeclaration),
     /// LRM 6.5.4 Interface subprogram declarations
     Subprogram(SubprogramDeclaration, Option<SubprogramDefault>),
     /// LRM 6.5.5 Interface package declaration
@@ -654,9 +715,12 @@ pub enum Declaration {
     Alias(AliasDeclaration),
     SubprogramDeclaration(SubprogramDeclaration),
     SubprogramBody(SubprogramBody),
+    SubprogramInstantiation(SubprogramInstantiation),
     Use(WithPos<UseClause>),
     Package(PackageInstantiation),
     Configuration(ConfigurationSpecification),
+    GroupTemplate(GroupTemplateDeclaration),
+    Group(GroupDeclaration),
 }
 
 /// LRM 10.2 Wait statement
@@ -789,6 +853,8 @@ pub struct CaseStatement {
     pub is_matching: bool,
     pub expression: WithPos<Expression>,
     pub alternatives: Vec<Alternative<Vec<LabeledSequentialStatement>>>,
+    /// The position of the `end` keyword of `end case`
+    pub end_pos: SrcPos,
 }
 
 /// LRM 10.10 Loop statement
@@ -997,6 +1063,8 @@ pub enum ContextItem {
 pub struct ContextDeclaration {
     pub ident: Ident,
     pub items: ContextClause,
+    /// The position of the optional trailing label such as `foo` in `end context foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 
 /// LRM 4.9 Package instatiation declaration
@@ -1093,6 +1161,8 @@ pub struct ConfigurationDeclaration {
     pub decl: Vec<ConfigurationDeclarativeItem>,
     pub vunit_bind_inds: Vec<VUnitBindingIndication>,
     pub block_config: BlockConfiguration,
+    /// The position of the optional trailing label such as `foo` in `end configuration foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 
 /// LRM 3.2 Entity declarations
@@ -1104,6 +1174,8 @@ pub struct EntityDeclaration {
     pub port_clause: Option<Vec<InterfaceDeclaration>>,
     pub decl: Vec<Declaration>,
     pub statements: Vec<LabeledConcurrentStatement>,
+    /// The position of the optional trailing label such as `foo` in `end entity foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 /// LRM 3.3 Architecture bodies
 #[derive(PartialEq, Debug, Clone)]
@@ -1113,6 +1185,8 @@ pub struct ArchitectureBody {
     pub entity_name: WithRef<Ident>,
     pub decl: Vec<Declaration>,
     pub statements: Vec<LabeledConcurrentStatement>,
+    /// The position of the optional trailing label such as `foo` in `end architecture foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 
 /// LRM 4.7 Package declarations
@@ -1122,6 +1196,8 @@ pub struct PackageDeclaration {
     pub ident: Ident,
     pub generic_clause: Option<Vec<InterfaceDeclaration>>,
     pub decl: Vec<Declaration>,
+    /// The position of the optional trailing label such as `foo` in `end package foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 
 /// LRM 4.8 Package bodies
@@ -1130,6 +1206,8 @@ pub struct PackageBody {
     pub context_clause: ContextClause,
     pub ident: WithRef<Ident>,
     pub decl: Vec<Declaration>,
+    /// The position of the optional trailing label such as `foo` in `end package body foo;`
+    pub end_ident_pos: Option<SrcPos>,
 }
 
 /// LRM 13.1 Design units
@@ -216,6 +216,56 @@ impl AnyDesignUnit {
             None
         }
     }
+
+    /// The library, use and context clauses preceding the design unit in source order
+    /// A context declaration has no context clause of its own
+    pub fn context_clauses(&self) -> &[WithPos<ContextItem>] {
+        match self {
+            AnyDesignUnit::Primary(ref primary) => match primary {
+                AnyPrimaryUnit::Entity(ref unit) => &unit.context_clause,
+                AnyPrimaryUnit::Configuration(ref unit) => &unit.context_clause,
+                AnyPrimaryUnit::Package(ref unit) => &unit.context_clause,
+                AnyPrimaryUnit::PackageInstance(ref unit) => &unit.context_clause,
+                AnyPrimaryUnit::Context(..) => &[],
+            },
+            AnyDesignUnit::Secondary(ref secondary) => match secondary {
+                AnySecondaryUnit::Architecture(ref unit) => &unit.context_clause,
+                AnySecondaryUnit::PackageBody(ref unit) => &unit.context_clause,
+            },
+        }
+    }
+
+    /// The position of the optional trailing label after `end` of the design unit
+    /// A package instance has no end label
+    pub fn end_ident_pos(&self) -> Option<&SrcPos> {
+        match self {
+            AnyDesignUnit::Primary(ref primary) => match primary {
+                AnyPrimaryUnit::Entity(ref unit) => unit.end_ident_pos.as_ref(),
+                AnyPrimaryUnit::Configuration(ref unit) => unit.end_ident_pos.as_ref(),
+                AnyPrimaryUnit::Package(ref unit) => unit.end_ident_pos.as_ref(),
+                AnyPrimaryUnit::PackageInstance(..) => None,
+                AnyPrimaryUnit::Context(ref unit) => unit.end_ident_pos.as_ref(),
+            },
+            AnyDesignUnit::Secondary(ref secondary) => match secondary {
+                AnySecondaryUnit::Architecture(ref unit) => unit.end_ident_pos.as_ref(),
+                AnySecondaryUnit::PackageBody(ref unit) => unit.end_ident_pos.as_ref(),
+            },
+        }
+    }
+}
+
+/// The position of the name of the design unit when the position is on its trailing end label,
+/// for example to go from `foo` in `end entity foo;` to the declaration of the entity
+pub fn declaration_for_end_label(unit: &AnyDesignUnit, pos: &SrcPos) -> Option<SrcPos> {
+    let end_ident_pos = unit.end_ident_pos()?;
+    if end_ident_pos.source == pos.source
+        && end_ident_pos.start() <= pos.start()
+        && pos.end() <= end_ident_pos.end()
+    {
+        Some(unit.pos().clone())
+    } else {
+        None
+    }
 }
 
 /// Upper case first letter
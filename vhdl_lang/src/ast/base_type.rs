@@ -0,0 +1,95 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! Resolution of subtype declarations to their base type within a declarative part
+use super::*;
+
+/// Follow subtype declarations within the scope down to the definition of the base type
+///
+/// Type marks are resolved by name within the scope only,
+/// None is returned when a type mark is declared elsewhere or when the subtypes form a cycle
+pub fn base_type<'a>(type_name: &Symbol, scope: &'a [Declaration]) -> Option<&'a TypeDefinition> {
+    let mut visited: Vec<&Symbol> = Vec::new();
+    let mut name = type_name;
+
+    loop {
+        if visited.contains(&name) {
+            return None;
+        }
+        visited.push(name);
+
+        match find_type_definition(name, scope)? {
+            TypeDefinition::Subtype(ref subtype_indication) => {
+                name = match subtype_indication.type_mark.item {
+                    SelectedName::Designator(ref designator) => match designator.item {
+                        Designator::Identifier(ref sym) => sym,
+                        _ => return None,
+                    },
+                    SelectedName::Selected(..) => return None,
+                };
+            }
+            def => return Some(def),
+        }
+    }
+}
+
+/// The full type declaration of the name, ignoring incomplete type declarations
+fn find_type_definition<'a>(name: &Symbol, scope: &'a [Declaration]) -> Option<&'a TypeDefinition> {
+    scope.iter().find_map(|decl| match decl {
+        Declaration::Type(TypeDeclaration { ref ident, ref def }) if &ident.item == name => {
+            if let TypeDefinition::Incomplete(..) = def {
+                None
+            } else {
+                Some(def)
+            }
+        }
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn base_type_of_subtype_of_subtype_of_record() {
+        let code = Code::new(
+            "
+type rec_t;
+type rec_t is record
+  field : natural;
+end record;
+subtype sub_t is rec_t;
+subtype sub_sub_t is sub_t;
+",
+        );
+        let decl = code.declarative_part();
+        let record = match decl[1] {
+            Declaration::Type(ref type_decl) => &type_decl.def,
+            _ => panic!("Expected type declaration"),
+        };
+
+        assert_eq!(base_type(&code.symbol("sub_sub_t"), &decl), Some(record));
+        assert_eq!(base_type(&code.symbol("rec_t"), &decl), Some(record));
+        assert_eq!(base_type(&code.symbol("natural"), &decl), None);
+    }
+
+    #[test]
+    fn base_type_of_subtype_cycle() {
+        let code = Code::new(
+            "
+subtype self_t is self_t;
+subtype a_t is b_t;
+subtype b_t is a_t;
+",
+        );
+        let decl = code.declarative_part();
+
+        assert_eq!(base_type(&code.symbol("self_t"), &decl), None);
+        assert_eq!(base_type(&code.symbol("a_t"), &decl), None);
+    }
+}
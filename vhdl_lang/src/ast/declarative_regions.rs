@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! Traversal of all declarative parts within a design unit
+use super::*;
+
+impl AnyDesignUnit {
+    /// The declarative parts of the design unit and of all nested
+    /// processes, blocks, generate statements, subprograms and protected type bodies
+    pub fn declarative_regions(&self) -> Vec<&[Declaration]> {
+        let mut regions = Vec::new();
+        match self {
+            AnyDesignUnit::Primary(unit) => match unit {
+                AnyPrimaryUnit::Entity(entity) => {
+                    add_declarative_part(&entity.decl, &mut regions);
+                    add_concurrent_part(&entity.statements, &mut regions);
+                }
+                AnyPrimaryUnit::Package(package) => {
+                    add_declarative_part(&package.decl, &mut regions);
+                }
+                AnyPrimaryUnit::Configuration(..)
+                | AnyPrimaryUnit::PackageInstance(..)
+                | AnyPrimaryUnit::Context(..) => {}
+            },
+            AnyDesignUnit::Secondary(unit) => match unit {
+                AnySecondaryUnit::Architecture(architecture) => {
+                    add_declarative_part(&architecture.decl, &mut regions);
+                    add_concurrent_part(&architecture.statements, &mut regions);
+                }
+                AnySecondaryUnit::PackageBody(body) => {
+                    add_declarative_part(&body.decl, &mut regions);
+                }
+            },
+        }
+        regions
+    }
+}
+
+fn add_declarative_part<'a>(decl: &'a [Declaration], regions: &mut Vec<&'a [Declaration]>) {
+    regions.push(decl);
+    for declaration in decl.iter() {
+        match declaration {
+            Declaration::SubprogramBody(ref body) => {
+                add_declarative_part(&body.declarations, regions);
+            }
+            Declaration::Type(TypeDeclaration {
+                def: TypeDefinition::ProtectedBody(ref body),
+                ..
+            }) => {
+                add_declarative_part(&body.decl, regions);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn add_generate_body<'a>(body: &'a GenerateBody, regions: &mut Vec<&'a [Declaration]>) {
+    if let Some(ref decl) = body.decl {
+        add_declarative_part(decl, regions);
+    }
+    add_concurrent_part(&body.statements, regions);
+}
+
+fn add_concurrent_part<'a>(
+    statements: &'a [LabeledConcurrentStatement],
+    regions: &mut Vec<&'a [Declaration]>,
+) {
+    for statement in statements.iter() {
+        match statement.statement {
+            ConcurrentStatement::Block(ref block) => {
+                add_declarative_part(&block.decl, regions);
+                add_concurrent_part(&block.statements, regions);
+            }
+            ConcurrentStatement::Process(ref process) => {
+                add_declarative_part(&process.decl, regions);
+            }
+            ConcurrentStatement::ForGenerate(ref gen) => {
+                add_generate_body(&gen.body, regions);
+            }
+            ConcurrentStatement::IfGenerate(ref gen) => {
+                for conditional in gen.conditionals.iter() {
+                    add_generate_body(&conditional.item, regions);
+                }
+                if let Some(ref else_item) = gen.else_item {
+                    add_generate_body(else_item, regions);
+                }
+            }
+            ConcurrentStatement::CaseGenerate(ref gen) => {
+                for alternative in gen.alternatives.iter() {
+                    add_generate_body(&alternative.item, regions);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn declarative_regions_include_nested_regions() {
+        let code = Code::new(
+            "
+architecture a of ent is
+  constant c0 : natural := 0;
+begin
+  main : process is
+    procedure proc is
+      variable v0 : natural;
+    begin
+    end procedure;
+  begin
+  end process;
+end architecture;
+",
+        );
+        let design_file = code.design_file();
+        let regions = design_file.design_units[0].declarative_regions();
+        assert_eq!(
+            regions.iter().map(|decl| decl.len()).collect::<Vec<_>>(),
+            vec![1, 1, 1]
+        );
+    }
+}
@@ -39,3 +39,834 @@ impl<T: Display> Display for WithPos<T> {
         write!(f, "{}", &self.item)
     }
 }
+
+impl Display for Name {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Name::Designator(ref des) => write!(f, "{}", des),
+            Name::Selected(ref prefix, ref des) => write!(f, "{}.{}", prefix, des),
+            Name::SelectedAll(ref prefix) => write!(f, "{}.all", prefix),
+            Name::Indexed(ref prefix, ref indexes) => {
+                write!(f, "{}(", prefix)?;
+                write_separated(f, indexes, ", ")?;
+                write!(f, ")")
+            }
+            Name::Slice(ref prefix, ref drange) => write!(f, "{}({})", prefix, drange),
+            Name::Attribute(ref attr) => write!(f, "{}", attr),
+            Name::FunctionCall(ref fcall) => write!(f, "{}", fcall),
+            Name::External(ref external) => write!(f, "{}", external),
+        }
+    }
+}
+
+impl Display for AttributeName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.name)?;
+        if let Some(ref signature) = self.signature {
+            write!(f, "{}", signature)?;
+        }
+        write!(f, "'{}", self.attr)?;
+        if let Some(ref expr) = self.expr {
+            write!(f, "({})", expr)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Signature::Function(ref args, ref ret) => {
+                write!(f, "[")?;
+                write_separated(f, args, ", ")?;
+                if args.is_empty() {
+                    write!(f, "return {}]", ret)
+                } else {
+                    write!(f, " return {}]", ret)
+                }
+            }
+            Signature::Procedure(ref args) => {
+                write!(f, "[")?;
+                write_separated(f, args, ", ")?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Display for ExternalName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let class = match self.class {
+            ExternalObjectClass::Constant => "constant",
+            ExternalObjectClass::Signal => "signal",
+            ExternalObjectClass::Variable => "variable",
+        };
+        write!(f, "<< {} ", class)?;
+        match self.path.item {
+            ExternalPath::Package(ref name) => write!(f, "@{}", name)?,
+            ExternalPath::Absolute(ref name) => write!(f, ".{}", name)?,
+            ExternalPath::Relative(ref name, up_levels) => {
+                write!(f, "{}{}", "^.".repeat(up_levels), name)?
+            }
+        }
+        write!(f, " : {} >>", self.subtype)
+    }
+}
+
+impl Display for FunctionCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}(", self.name)?;
+        write_separated(f, &self.parameters, ", ")?;
+        write!(f, ")")
+    }
+}
+
+impl Display for AssociationElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(ref formal) = self.formal {
+            write!(f, "{} => ", formal)?;
+        }
+        write!(f, "{}", self.actual)
+    }
+}
+
+impl Display for ActualPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ActualPart::Expression(ref expr) => write!(f, "{}", expr),
+            ActualPart::Open => write!(f, "open"),
+        }
+    }
+}
+
+impl Display for Choice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Choice::Expression(ref expr) => write!(f, "{}", expr),
+            Choice::DiscreteRange(ref drange) => write!(f, "{}", drange),
+            Choice::Others => write!(f, "others"),
+        }
+    }
+}
+
+impl Display for ElementAssociation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ElementAssociation::Positional(ref expr) => write!(f, "{}", expr),
+            ElementAssociation::Named(ref choices, ref expr) => {
+                write_separated(f, choices, " | ")?;
+                write!(f, " => {}", expr)
+            }
+        }
+    }
+}
+
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Literal::String(ref value) => {
+                write!(f, "\"{}\"", value.to_string().replace('"', "\"\""))
+            }
+            Literal::BitString(ref value) => write!(f, "{}", value),
+            Literal::Character(byte) => write!(f, "'{}'", *byte as char),
+            Literal::AbstractLiteral(ref value) => write!(f, "{}", value),
+            Literal::Physical(ref value, ref unit) => write!(f, "{} {}", value, unit),
+            Literal::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl Display for AbstractLiteral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AbstractLiteral::Integer(value) => write!(f, "{}", value),
+            AbstractLiteral::Real(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+impl Display for BitString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(length) = self.length {
+            write!(f, "{}", length)?;
+        }
+        let base = match self.base {
+            BaseSpecifier::B => "b",
+            BaseSpecifier::O => "o",
+            BaseSpecifier::X => "x",
+            BaseSpecifier::UB => "ub",
+            BaseSpecifier::UO => "uo",
+            BaseSpecifier::UX => "ux",
+            BaseSpecifier::SB => "sb",
+            BaseSpecifier::SO => "so",
+            BaseSpecifier::SX => "sx",
+            BaseSpecifier::D => "d",
+        };
+        write!(f, "{}\"{}\"", base, self.value)
+    }
+}
+
+impl Display for Unary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let op = match self {
+            Unary::And => "and",
+            Unary::Or => "or",
+            Unary::Nand => "nand",
+            Unary::Nor => "nor",
+            Unary::Xor => "xor",
+            Unary::Xnor => "xnor",
+            Unary::Abs => "abs",
+            Unary::Not => "not",
+            Unary::Minus => "-",
+            Unary::Plus => "+",
+            Unary::QueQue => "??",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+impl Display for Binary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let op = match self {
+            Binary::And => "and",
+            Binary::Or => "or",
+            Binary::Nand => "nand",
+            Binary::Nor => "nor",
+            Binary::Xor => "xor",
+            Binary::Xnor => "xnor",
+            Binary::EQ => "=",
+            Binary::NE => "/=",
+            Binary::LT => "<",
+            Binary::LTE => "<=",
+            Binary::GT => ">",
+            Binary::GTE => ">=",
+            Binary::QueEQ => "?=",
+            Binary::QueNE => "?/=",
+            Binary::QueLT => "?<",
+            Binary::QueLTE => "?<=",
+            Binary::QueGT => "?>",
+            Binary::QueGTE => "?>=",
+            Binary::SLL => "sll",
+            Binary::SRL => "srl",
+            Binary::SLA => "sla",
+            Binary::SRA => "sra",
+            Binary::ROL => "rol",
+            Binary::ROR => "ror",
+            Binary::Plus => "+",
+            Binary::Minus => "-",
+            Binary::Concat => "&",
+            Binary::Times => "*",
+            Binary::Div => "/",
+            Binary::Mod => "mod",
+            Binary::Rem => "rem",
+            Binary::Pow => "**",
+        };
+        write!(f, "{}", op)
+    }
+}
+
+/// LRM 9.2 operator precedence, a higher value binds tighter
+fn binary_precedence(op: Binary) -> usize {
+    match op {
+        Binary::And | Binary::Or | Binary::Nand | Binary::Nor | Binary::Xor | Binary::Xnor => 1,
+        Binary::EQ
+        | Binary::NE
+        | Binary::LT
+        | Binary::LTE
+        | Binary::GT
+        | Binary::GTE
+        | Binary::QueEQ
+        | Binary::QueNE
+        | Binary::QueLT
+        | Binary::QueLTE
+        | Binary::QueGT
+        | Binary::QueGTE => 2,
+        Binary::SLL | Binary::SRL | Binary::SLA | Binary::SRA | Binary::ROL | Binary::ROR => 3,
+        Binary::Plus | Binary::Minus | Binary::Concat => 4,
+        Binary::Times | Binary::Div | Binary::Mod | Binary::Rem => 5,
+        Binary::Pow => 6,
+    }
+}
+
+fn write_operand(f: &mut Formatter<'_>, expr: &Expression, parenthesize: bool) -> Result {
+    if parenthesize {
+        write!(f, "({})", expr)
+    } else {
+        write!(f, "{}", expr)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Expression::Binary(op, ref lhs, ref rhs) => {
+                let precedence = binary_precedence(*op);
+                let operand_precedence = |expr: &Expression| match expr {
+                    Expression::Binary(op, ..) => Some(binary_precedence(*op)),
+                    Expression::Unary(..) => Some(0),
+                    _ => None,
+                };
+
+                // Left associative, the right operand needs parenthesis at the same precedence
+                let lhs_parens = operand_precedence(&lhs.item)
+                    .map(|lhs| lhs < precedence || (lhs == precedence && precedence == 1))
+                    .unwrap_or(false);
+                let rhs_parens = operand_precedence(&rhs.item)
+                    .map(|rhs| rhs <= precedence)
+                    .unwrap_or(false);
+
+                write_operand(f, &lhs.item, lhs_parens)?;
+                write!(f, " {} ", op)?;
+                write_operand(f, &rhs.item, rhs_parens)
+            }
+            Expression::Unary(op, ref expr) => {
+                match op {
+                    Unary::Minus | Unary::Plus => write!(f, "{}", op)?,
+                    _ => write!(f, "{} ", op)?,
+                }
+                let parens = match expr.item {
+                    Expression::Binary(..) | Expression::Unary(..) => true,
+                    _ => false,
+                };
+                write_operand(f, &expr.item, parens)
+            }
+            Expression::Aggregate(ref assocs) => {
+                write!(f, "(")?;
+                write_separated(f, assocs, ", ")?;
+                write!(f, ")")
+            }
+            Expression::Qualified(ref qexpr) => write!(f, "{}", qexpr),
+            Expression::Name(ref name) => write!(f, "{}", name),
+            Expression::Literal(ref literal) => write!(f, "{}", literal),
+            Expression::New(ref alloc) => write!(f, "new {}", alloc),
+        }
+    }
+}
+
+impl Display for QualifiedExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.expr.item {
+            Expression::Aggregate(..) => write!(f, "{}'{}", self.name, self.expr),
+            _ => write!(f, "{}'({})", self.name, self.expr),
+        }
+    }
+}
+
+impl Display for Allocator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Allocator::Qualified(ref qexpr) => write!(f, "{}", qexpr),
+            Allocator::Subtype(ref subtype) => write!(f, "{}", subtype),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Direction::Ascending => write!(f, "to"),
+            Direction::Descending => write!(f, "downto"),
+        }
+    }
+}
+
+impl Display for RangeConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.left_expr, self.direction, self.right_expr
+        )
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Range::Range(ref constraint) => write!(f, "{}", constraint),
+            Range::Attribute(ref attr) => write!(f, "{}", attr),
+        }
+    }
+}
+
+impl Display for DiscreteRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            DiscreteRange::Discrete(ref type_mark, ref range) => {
+                write!(f, "{}", type_mark)?;
+                if let Some(ref range) = range {
+                    write!(f, " range {}", range)?;
+                }
+                Ok(())
+            }
+            DiscreteRange::Range(ref range) => write!(f, "{}", range),
+        }
+    }
+}
+
+impl Display for ElementConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}{}", self.ident, self.constraint)
+    }
+}
+
+impl Display for SubtypeConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SubtypeConstraint::Range(ref range) => write!(f, " range {}", range),
+            SubtypeConstraint::Array(ref dranges, ref constraint) => {
+                if dranges.is_empty() {
+                    write!(f, "(open)")?;
+                } else {
+                    write!(f, "(")?;
+                    write_separated(f, dranges, ", ")?;
+                    write!(f, ")")?;
+                }
+                if let Some(ref constraint) = constraint {
+                    write!(f, "{}", constraint)?;
+                }
+                Ok(())
+            }
+            SubtypeConstraint::Record(ref constraints) => {
+                write!(f, "(")?;
+                write_separated(f, constraints, ", ")?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl Display for RecordElementResolution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match *self.resolution {
+            ResolutionIndication::FunctionName(ref name) => write!(f, "{} {}", self.ident, name),
+            ref resolution => write!(f, "{} {}", self.ident, resolution),
+        }
+    }
+}
+
+impl Display for ResolutionIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ResolutionIndication::FunctionName(ref name) => write!(f, "{}", name),
+            ResolutionIndication::ArrayElement(ref name) => write!(f, "({})", name),
+            ResolutionIndication::Record(ref resolutions) => {
+                write!(f, "(")?;
+                write_separated(f, resolutions, ", ")?;
+                write!(f, ")")
+            }
+            ResolutionIndication::Unresolved => Ok(()),
+        }
+    }
+}
+
+impl Display for SubtypeIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.resolution != ResolutionIndication::Unresolved {
+            write!(f, "{} ", self.resolution)?;
+        }
+        write!(f, "{}", self.type_mark)?;
+        if let Some(ref constraint) = self.constraint {
+            write!(f, "{}", constraint)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for EnumerationLiteral {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EnumerationLiteral::Identifier(ref sym) => write!(f, "{}", sym),
+            EnumerationLiteral::Character(byte) => write!(f, "'{}'", *byte as char),
+        }
+    }
+}
+
+impl Display for ArrayIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ArrayIndex::IndexSubtypeDefintion(ref type_mark) => write!(f, "{} range <>", type_mark),
+            ArrayIndex::Discrete(ref drange) => write!(f, "{}", drange),
+        }
+    }
+}
+
+impl Display for ElementDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{} : {}", self.ident, self.subtype)
+    }
+}
+
+impl Display for UseClause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "use ")?;
+        write_separated(f, &self.name_list, ", ")
+    }
+}
+
+impl Display for EntityName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EntityName::Name(ref tag) => {
+                write!(f, "{}", tag.designator)?;
+                if let Some(ref signature) = tag.signature {
+                    write!(f, "{}", signature)?;
+                }
+                Ok(())
+            }
+            EntityName::All => write!(f, "all"),
+            EntityName::Others => write!(f, "others"),
+        }
+    }
+}
+
+impl Display for AttributeSpecification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "attribute {} of {} : {} is {}",
+            self.ident, self.entity_name, self.entity_class, self.expr
+        )
+    }
+}
+
+impl Display for ProtectedTypeDeclarativeItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ProtectedTypeDeclarativeItem::Subprogram(ref decl) => write!(f, "{}", decl),
+            ProtectedTypeDeclarativeItem::Use(ref clause) => write!(f, "{}", clause),
+            ProtectedTypeDeclarativeItem::AttributeSpecification(ref spec) => {
+                write!(f, "{}", spec)
+            }
+        }
+    }
+}
+
+impl Display for TypeDefinition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            TypeDefinition::Enumeration(ref literals) => {
+                write!(f, "(")?;
+                write_separated(f, literals, ", ")?;
+                write!(f, ")")
+            }
+            TypeDefinition::Integer(ref range) => write!(f, "range {}", range),
+            TypeDefinition::Physical(ref physical) => {
+                write!(
+                    f,
+                    "range {} units {};",
+                    physical.range, physical.primary_unit
+                )?;
+                for (ident, literal) in physical.secondary_units.iter() {
+                    write!(f, " {} = {};", ident, literal)?;
+                }
+                write!(f, " end units")
+            }
+            TypeDefinition::Array(ref indexes, ref subtype) => {
+                write!(f, "array (")?;
+                write_separated(f, indexes, ", ")?;
+                write!(f, ") of {}", subtype)
+            }
+            TypeDefinition::Record(ref elements) => {
+                write!(f, "record")?;
+                for element in elements.iter() {
+                    write!(f, " {};", element)?;
+                }
+                write!(f, " end record")
+            }
+            TypeDefinition::Access(ref subtype) => write!(f, "access {}", subtype),
+            TypeDefinition::Incomplete(..) => Ok(()),
+            TypeDefinition::File(ref type_mark) => write!(f, "file of {}", type_mark),
+            TypeDefinition::Protected(ref protected) => {
+                write!(f, "protected")?;
+                for item in protected.items.iter() {
+                    write!(f, " {};", item)?;
+                }
+                write!(f, " end protected")
+            }
+            // The declarations of a protected type body are not rendered
+            TypeDefinition::ProtectedBody(..) => write!(f, "protected body end protected body"),
+            TypeDefinition::Subtype(ref subtype) => write!(f, "{}", subtype),
+        }
+    }
+}
+
+impl Display for TypeDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.def {
+            TypeDefinition::Subtype(ref subtype) => {
+                write!(f, "subtype {} is {}", self.ident, subtype)
+            }
+            TypeDefinition::Incomplete(..) => write!(f, "type {}", self.ident),
+            ref def => write!(f, "type {} is {}", self.ident, def),
+        }
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Mode::In => write!(f, "in"),
+            Mode::Out => write!(f, "out"),
+            Mode::InOut => write!(f, "inout"),
+            Mode::Buffer => write!(f, "buffer"),
+            Mode::Linkage => write!(f, "linkage"),
+        }
+    }
+}
+
+impl Display for EntityClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EntityClass::Entity => write!(f, "entity"),
+            EntityClass::Architecture => write!(f, "architecture"),
+            EntityClass::Configuration => write!(f, "configuration"),
+            EntityClass::Package => write!(f, "package"),
+            EntityClass::Signal => write!(f, "signal"),
+            EntityClass::Variable => write!(f, "variable"),
+            EntityClass::Procedure => write!(f, "procedure"),
+            EntityClass::Function => write!(f, "function"),
+            EntityClass::Component => write!(f, "component"),
+            EntityClass::Constant => write!(f, "constant"),
+            EntityClass::Type => write!(f, "type"),
+            EntityClass::Subtype => write!(f, "subtype"),
+            EntityClass::Label => write!(f, "label"),
+            EntityClass::Units => write!(f, "units"),
+            EntityClass::Group => write!(f, "group"),
+            EntityClass::File => write!(f, "file"),
+        }
+    }
+}
+
+impl Display for SubprogramDesignator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SubprogramDesignator::Identifier(ref sym) => write!(f, "{}", sym),
+            SubprogramDesignator::OperatorSymbol(ref latin1) => write!(f, "\"{}\"", latin1),
+        }
+    }
+}
+
+impl Display for SubprogramDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SubprogramDeclaration::Procedure(ref proc) => {
+                write!(f, "procedure {}", proc.designator)?;
+                if !proc.parameter_list.is_empty() {
+                    write!(f, "{}", format_list(&proc.parameter_list, true))?;
+                }
+                Ok(())
+            }
+            SubprogramDeclaration::Function(ref fun) => {
+                if !fun.pure {
+                    write!(f, "impure ")?;
+                }
+                write!(f, "function {}", fun.designator)?;
+                if !fun.parameter_list.is_empty() {
+                    write!(f, "{}", format_list(&fun.parameter_list, true))?;
+                }
+                write!(f, " return {}", fun.return_type)
+            }
+        }
+    }
+}
+
+/// Object classes are only rendered when they differ from the default of the list,
+/// signals are the default of port lists but must be explicit in parameter lists
+fn interface_object_class(
+    object: &InterfaceObjectDeclaration,
+    is_parameter: bool,
+) -> Option<&'static str> {
+    match (object.class, object.mode) {
+        (ObjectClass::Signal, _) if is_parameter => Some("signal"),
+        (ObjectClass::Signal, _) | (ObjectClass::Constant, Mode::In) => None,
+        (ObjectClass::Variable, Mode::In) => Some("variable"),
+        (ObjectClass::Variable, _) => None,
+        (ObjectClass::Constant, _) => Some("constant"),
+        (ObjectClass::SharedVariable, _) => Some("shared variable"),
+    }
+}
+
+/// The mode, subtype and default value of an interface object
+fn interface_object_tail(object: &InterfaceObjectDeclaration) -> String {
+    let mut tail = format!(" : {} {}", object.mode, object.subtype_indication);
+    if let Some(ref expr) = object.expression {
+        tail.push_str(&format!(" := {}", expr));
+    }
+    tail
+}
+
+impl Display for InterfaceDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            InterfaceDeclaration::Object(ref object) => {
+                if let Some(class) = interface_object_class(object, false) {
+                    write!(f, "{} ", class)?;
+                }
+                write!(f, "{}{}", object.ident, interface_object_tail(object))
+            }
+            InterfaceDeclaration::File(ref file) => {
+                write!(f, "file {} : {}", file.ident, file.subtype_indication)
+            }
+            InterfaceDeclaration::Type(ref typ) => write!(f, "type {}", typ.ident),
+            InterfaceDeclaration::Subprogram(ref decl, ref default) => {
+                write!(f, "{}", decl)?;
+                match default {
+                    Some(SubprogramDefault::Name(ref name)) => write!(f, " is {}", name),
+                    Some(SubprogramDefault::Box) => write!(f, " is <>"),
+                    None => Ok(()),
+                }
+            }
+            InterfaceDeclaration::Package(ref package) => {
+                write!(
+                    f,
+                    "package {} is new {} generic map (",
+                    package.ident, package.package_name
+                )?;
+                match package.generic_map {
+                    InterfacePackageGenericMapAspect::Map(ref assocs) => {
+                        write_separated(f, assocs, ", ")?
+                    }
+                    InterfacePackageGenericMapAspect::Box => write!(f, "<>")?,
+                    InterfacePackageGenericMapAspect::Default => write!(f, "default")?,
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl Display for ContextReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "context ")?;
+        write_separated(f, &self.name_list, ", ")
+    }
+}
+
+/// Render an interface list in a canonical form such as `(a, b : in std_logic; c : out std_logic)`
+/// Consecutive objects of the same class, mode, subtype and default value share a declaration
+pub fn format_interface_list(list: &[InterfaceDeclaration]) -> String {
+    format_list(list, false)
+}
+
+fn format_list(list: &[InterfaceDeclaration], is_parameter: bool) -> String {
+    let mut groups: Vec<String> = Vec::new();
+    let mut previous: Option<(Option<&'static str>, String)> = None;
+
+    for decl in list.iter() {
+        if let InterfaceDeclaration::Object(ref object) = decl {
+            let class = interface_object_class(object, is_parameter);
+            let key = (class, interface_object_tail(object));
+
+            if let (Some(prev_key), Some(group)) = (previous.as_ref(), groups.last_mut()) {
+                if *prev_key == key {
+                    let idx = group.len() - key.1.len();
+                    group.insert_str(idx, &format!(", {}", object.ident));
+                    continue;
+                }
+            }
+
+            let mut group = String::new();
+            if let Some(class) = class {
+                group.push_str(class);
+                group.push(' ');
+            }
+            group.push_str(&format!("{}{}", object.ident, key.1));
+            groups.push(group);
+            previous = Some(key);
+        } else {
+            groups.push(decl.to_string());
+            previous = None;
+        }
+    }
+
+    format!("({})", groups.join("; "))
+}
+
+fn write_separated<T: Display>(f: &mut Formatter<'_>, items: &[T], separator: &str) -> Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn format_mixed_port_list() {
+        let code = Code::new(
+            "\
+(a, b : in std_logic;
+ c : out std_logic;
+ d : in  STD_LOGIC_VECTOR(7 downto 0) := (others => '0');
+ e : inout resolved integer range 0 to 2**w - 1)",
+        );
+
+        assert_eq!(
+            format_interface_list(&code.port_list()),
+            "(a, b : in std_logic; c : out std_logic; \
+             d : in STD_LOGIC_VECTOR(7 downto 0) := (others => '0'); \
+             e : inout resolved integer range 0 to 2 ** w - 1)"
+        );
+    }
+
+    #[test]
+    fn format_type_declarations() {
+        let code = Code::new(
+            "\
+type state_t is (idle, busy, 'x');
+type int_t is range 0 to 7;
+type time_t is range 0 to 1000 units fs; ps = 1000 fs; end units;
+type arr_t is array (natural range <>, 0 to 3) of bit;
+type rec_t is record a, b : bit; c : int_t; end record;
+type ptr_t is access rec_t;
+type file_t is file of string;
+type prot_t is protected procedure proc(x : bit); end protected;
+type incomplete_t;
+subtype sub_t is int_t range 0 to 3;",
+        );
+
+        let types: Vec<_> = code
+            .declarative_part()
+            .into_iter()
+            .map(|decl| match decl {
+                Declaration::Type(typ) => typ.to_string(),
+                _ => panic!("Expected type declaration"),
+            })
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                "type state_t is (idle, busy, 'x')",
+                "type int_t is range 0 to 7",
+                "type time_t is range 0 to 1000 units fs; ps = 1000 fs; end units",
+                "type arr_t is array (natural range <>, 0 to 3) of bit",
+                "type rec_t is record a : bit; b : bit; c : int_t; end record",
+                "type ptr_t is access rec_t",
+                "type file_t is file of string",
+                "type prot_t is protected procedure proc(x : in bit); end protected",
+                "type incomplete_t",
+                "subtype sub_t is int_t range 0 to 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_subprogram_parameter_list() {
+        let code = Code::new(
+            "procedure proc(constant x, y : in bit; variable z : bit; signal w : out bit)",
+        );
+
+        assert_eq!(
+            code.subprogram_decl().to_string(),
+            "procedure proc(x, y : in bit; variable z : in bit; signal w : out bit)"
+        );
+    }
+}
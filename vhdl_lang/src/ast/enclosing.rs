@@ -0,0 +1,400 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! Lookup of the innermost named construct enclosing a source position
+use super::search::*;
+use super::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ConstructKind {
+    Entity,
+    Architecture,
+    Package,
+    PackageBody,
+    Subprogram,
+    Process,
+    Block,
+}
+
+/// Find the innermost named construct of the design unit which contains the position
+///
+/// The AST does not store the full extent of declarations and statements. A nested
+/// construct is therefore considered to extend from its first source position until
+/// the first source position of the next declaration or statement in the same region.
+pub fn enclosing_construct(
+    unit: &AnyDesignUnit,
+    pos: &SrcPos,
+) -> Option<(ConstructKind, Designator, SrcPos)> {
+    find_enclosing(unit, pos).map(|enclosing| enclosing.construct)
+}
+
+/// The declarative parts of the design unit and of its nested constructs which contain the position,
+/// outermost first
+pub fn enclosing_declarations<'a>(unit: &'a AnyDesignUnit, pos: &SrcPos) -> Vec<&'a [Declaration]> {
+    find_enclosing(unit, pos)
+        .map(|enclosing| enclosing.declarations)
+        .unwrap_or_default()
+}
+
+/// The innermost named construct and the declarative parts enclosing a position
+struct Enclosing<'a> {
+    construct: (ConstructKind, Designator, SrcPos),
+    declarations: Vec<&'a [Declaration]>,
+}
+
+fn find_enclosing<'a>(unit: &'a AnyDesignUnit, pos: &SrcPos) -> Option<Enclosing<'a>> {
+    let (kind, ident, end_ident_pos, decl, statements): (_, _, _, _, &[_]) = match unit {
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(entity)) => (
+            ConstructKind::Entity,
+            &entity.ident,
+            &entity.end_ident_pos,
+            &entity.decl,
+            &entity.statements,
+        ),
+        AnyDesignUnit::Primary(AnyPrimaryUnit::Package(package)) => (
+            ConstructKind::Package,
+            &package.ident,
+            &package.end_ident_pos,
+            &package.decl,
+            &[],
+        ),
+        AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(architecture)) => (
+            ConstructKind::Architecture,
+            &architecture.ident,
+            &architecture.end_ident_pos,
+            &architecture.decl,
+            &architecture.statements,
+        ),
+        AnyDesignUnit::Secondary(AnySecondaryUnit::PackageBody(body)) => (
+            ConstructKind::PackageBody,
+            &body.ident.item,
+            &body.end_ident_pos,
+            &body.decl,
+            &[],
+        ),
+        AnyDesignUnit::Primary(AnyPrimaryUnit::PackageInstance(..))
+        | AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(..))
+        | AnyDesignUnit::Primary(AnyPrimaryUnit::Context(..)) => return None,
+    };
+
+    if ident.pos.source() != pos.source() {
+        return None;
+    }
+
+    let start = Bounds::of(unit)
+        .start
+        .map_or(ident.pos.start(), |start| start.min(ident.pos.start()));
+    let end = end_ident_pos
+        .as_ref()
+        .map(|end_ident_pos| end_ident_pos.end());
+    if pos.start() < start || !is_before(pos.start(), end) {
+        return None;
+    }
+
+    let mut found = Enclosing {
+        construct: (
+            kind,
+            Designator::Identifier(ident.item.clone()),
+            ident.pos.clone(),
+        ),
+        declarations: Vec::new(),
+    };
+    find_in_region(decl, statements, end, pos.start(), &mut found);
+    Some(found)
+}
+
+/// The end is exclusive and a missing end means the region extends until the end of the design unit
+fn is_before(pos: Position, end: Option<Position>) -> bool {
+    match end {
+        Some(end) => pos < end,
+        None => true,
+    }
+}
+
+/// Collects the first and last source position within a part of the AST
+#[derive(Default)]
+struct Bounds {
+    start: Option<Position>,
+    end: Option<Position>,
+}
+
+impl Bounds {
+    fn of(item: &impl Search) -> Bounds {
+        let mut bounds = Bounds::default();
+        let _ = item.search(&mut bounds);
+        bounds
+    }
+
+    fn add(&mut self, pos: &SrcPos) {
+        self.start = Some(
+            self.start
+                .map_or(pos.start(), |start| start.min(pos.start())),
+        );
+        self.end = Some(self.end.map_or(pos.end(), |end| end.max(pos.end())));
+    }
+}
+
+impl Searcher for Bounds {
+    fn search_pos_with_ref(&mut self, pos: &SrcPos, _ref: &Reference) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+
+    fn search_decl_pos(&mut self, pos: &SrcPos) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+
+    fn search_with_pos(&mut self, pos: &SrcPos) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+
+    fn search_expression(&mut self, pos: &SrcPos, _expr: &Expression) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+
+    fn search_name(&mut self, pos: &SrcPos, _name: &Name) -> SearchState {
+        self.add(pos);
+        NotFinished
+    }
+}
+
+enum RegionItem<'a> {
+    Declaration(&'a Declaration),
+    Statement(&'a LabeledConcurrentStatement),
+    SequentialStatement(&'a LabeledSequentialStatement),
+    GenerateBody(&'a GenerateBody),
+}
+
+impl<'a> RegionItem<'a> {
+    fn start(&self) -> Option<Position> {
+        match self {
+            RegionItem::Declaration(decl) => Bounds::of(*decl).start,
+            RegionItem::Statement(statement) => Bounds::of(*statement).start,
+            RegionItem::SequentialStatement(statement) => Bounds::of(*statement).start,
+            RegionItem::GenerateBody(body) => Bounds::of(*body).start,
+        }
+    }
+}
+
+/// Find the item of a region containing the position where each item extends
+/// until the start of the next item or the end of the region
+fn find_in_items<'a>(
+    items: impl Iterator<Item = RegionItem<'a>>,
+    end: Option<Position>,
+    pos: Position,
+    found: &mut Enclosing<'a>,
+) {
+    let items: Vec<_> = items
+        .filter_map(|item| Some((item.start()?, item)))
+        .collect();
+
+    for (idx, (start, item)) in items.iter().enumerate() {
+        let item_end = items
+            .get(idx + 1)
+            .map_or(end, |(next_start, _)| Some(*next_start));
+
+        if *start <= pos && is_before(pos, item_end) {
+            find_in_item(item, item_end, pos, found);
+            return;
+        }
+    }
+}
+
+fn find_in_region<'a>(
+    decl: &'a [Declaration],
+    statements: &'a [LabeledConcurrentStatement],
+    end: Option<Position>,
+    pos: Position,
+    found: &mut Enclosing<'a>,
+) {
+    found.declarations.push(decl);
+    find_in_items(
+        decl.iter()
+            .map(RegionItem::Declaration)
+            .chain(statements.iter().map(RegionItem::Statement)),
+        end,
+        pos,
+        found,
+    );
+}
+
+fn find_in_sequential_region<'a>(
+    decl: &'a [Declaration],
+    statements: &'a [LabeledSequentialStatement],
+    end: Option<Position>,
+    pos: Position,
+    found: &mut Enclosing<'a>,
+) {
+    found.declarations.push(decl);
+    find_in_items(
+        decl.iter()
+            .map(RegionItem::Declaration)
+            .chain(statements.iter().map(RegionItem::SequentialStatement)),
+        end,
+        pos,
+        found,
+    );
+}
+
+fn find_in_item<'a>(
+    item: &RegionItem<'a>,
+    end: Option<Position>,
+    pos: Position,
+    found: &mut Enclosing<'a>,
+) {
+    match item {
+        RegionItem::Declaration(decl) => match decl {
+            Declaration::SubprogramBody(ref body) => {
+                found.construct = (
+                    ConstructKind::Subprogram,
+                    body.specification.designator().item,
+                    body.specification.pos().clone(),
+                );
+                find_in_sequential_region(&body.declarations, &body.statements, end, pos, found);
+            }
+            Declaration::Type(TypeDeclaration {
+                def: TypeDefinition::ProtectedBody(ref body),
+                ..
+            }) => {
+                find_in_region(&body.decl, &[], end, pos, found);
+            }
+            _ => {}
+        },
+        RegionItem::Statement(statement) => {
+            let named = |kind| {
+                statement.label.as_ref().map(|label| {
+                    (
+                        kind,
+                        Designator::Identifier(label.item.clone()),
+                        label.pos.clone(),
+                    )
+                })
+            };
+
+            match statement.statement {
+                ConcurrentStatement::Block(ref block) => {
+                    if let Some(block_found) = named(ConstructKind::Block) {
+                        found.construct = block_found;
+                    }
+                    find_in_region(&block.decl, &block.statements, end, pos, found);
+                }
+                ConcurrentStatement::Process(ref process) => {
+                    if let Some(process_found) = named(ConstructKind::Process) {
+                        found.construct = process_found;
+                    }
+                    find_in_sequential_region(&process.decl, &process.statements, end, pos, found);
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    find_in_items(
+                        std::iter::once(RegionItem::GenerateBody(&gen.body)),
+                        end,
+                        pos,
+                        found,
+                    );
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    find_in_items(
+                        gen.conditionals
+                            .iter()
+                            .map(|conditional| RegionItem::GenerateBody(&conditional.item))
+                            .chain(gen.else_item.iter().map(RegionItem::GenerateBody)),
+                        end,
+                        pos,
+                        found,
+                    );
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    find_in_items(
+                        gen.alternatives
+                            .iter()
+                            .map(|alternative| RegionItem::GenerateBody(&alternative.item)),
+                        end,
+                        pos,
+                        found,
+                    );
+                }
+                _ => {}
+            }
+        }
+        RegionItem::SequentialStatement(..) => {}
+        RegionItem::GenerateBody(body) => {
+            find_in_region(
+                body.decl.as_deref().unwrap_or(&[]),
+                &body.statements,
+                end,
+                pos,
+                found,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn position_in_nested_subprogram_is_enclosed_by_subprogram() {
+        let code = Code::new(
+            "
+architecture a of ent is
+begin
+  main : process is
+    procedure outer is
+      procedure inner is
+        variable v0 : natural;
+      begin
+        v0 := 0;
+      end procedure;
+    begin
+      done := true;
+    end procedure;
+  begin
+  end process;
+end architecture;
+",
+        );
+        let design_file = code.design_file();
+        let unit = &design_file.design_units[0];
+
+        assert_eq!(
+            enclosing_construct(unit, &code.s("v0", 2).pos()),
+            Some((
+                ConstructKind::Subprogram,
+                Designator::Identifier(code.symbol("inner")),
+                code.s1("inner").pos()
+            ))
+        );
+        assert_eq!(
+            enclosing_construct(unit, &code.s1("done").pos()),
+            Some((
+                ConstructKind::Subprogram,
+                Designator::Identifier(code.symbol("outer")),
+                code.s1("outer").pos()
+            ))
+        );
+        assert_eq!(
+            enclosing_construct(unit, &code.s1("main").pos()),
+            Some((
+                ConstructKind::Process,
+                Designator::Identifier(code.symbol("main")),
+                code.s1("main").pos()
+            ))
+        );
+        assert_eq!(
+            enclosing_construct(unit, &code.s1("ent").pos()),
+            Some((
+                ConstructKind::Architecture,
+                Designator::Identifier(code.symbol("a")),
+                code.s1("a of").s1("a").pos()
+            ))
+        );
+    }
+}
@@ -0,0 +1,362 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use fnv::FnvHasher;
+use std::hash::{Hash, Hasher};
+
+/// Compute a fingerprint of the externally visible interface of a design unit
+///
+/// Only the parts which other design units may depend upon are hashed, such as the
+/// generics and ports of an entity or the declarations of a package. Source positions
+/// and the bodies of architectures and packages do not affect the fingerprint.
+pub fn interface_fingerprint(unit: &AnyDesignUnit) -> u64 {
+    let mut hasher = FnvHasher::default();
+
+    match unit {
+        AnyDesignUnit::Primary(primary) => match primary {
+            AnyPrimaryUnit::Entity(entity) => {
+                "entity".hash(&mut hasher);
+                hash_context_clause(&entity.context_clause, &mut hasher);
+                entity.ident.item.name_utf8().hash(&mut hasher);
+                hash_interface_clause(&entity.generic_clause, &mut hasher);
+                hash_interface_clause(&entity.port_clause, &mut hasher);
+            }
+            AnyPrimaryUnit::Package(package) => {
+                "package".hash(&mut hasher);
+                hash_context_clause(&package.context_clause, &mut hasher);
+                package.ident.item.name_utf8().hash(&mut hasher);
+                hash_interface_clause(&package.generic_clause, &mut hasher);
+                for decl in package.decl.iter() {
+                    hash_declaration(decl, &mut hasher);
+                }
+            }
+            AnyPrimaryUnit::PackageInstance(instance) => {
+                "package instance".hash(&mut hasher);
+                hash_context_clause(&instance.context_clause, &mut hasher);
+                instance.ident.item.name_utf8().hash(&mut hasher);
+                instance.package_name.to_string().hash(&mut hasher);
+            }
+            AnyPrimaryUnit::Configuration(config) => {
+                "configuration".hash(&mut hasher);
+                config.ident.item.name_utf8().hash(&mut hasher);
+            }
+            AnyPrimaryUnit::Context(context) => {
+                "context".hash(&mut hasher);
+                hash_context_clause(&context.items, &mut hasher);
+                context.ident.item.name_utf8().hash(&mut hasher);
+            }
+        },
+        // Secondary units have no interface of their own
+        AnyDesignUnit::Secondary(secondary) => {
+            match secondary {
+                AnySecondaryUnit::Architecture(..) => "architecture",
+                AnySecondaryUnit::PackageBody(..) => "package body",
+            }
+            .hash(&mut hasher);
+            secondary.primary_ident().item.name_utf8().hash(&mut hasher);
+            secondary.ident().item.name_utf8().hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+fn hash_context_clause(context_clause: &[WithPos<ContextItem>], hasher: &mut impl Hasher) {
+    for item in context_clause.iter() {
+        match item.item {
+            ContextItem::Library(ref clause) => {
+                "library".hash(hasher);
+                for name in clause.name_list.iter() {
+                    name.item.name_utf8().hash(hasher);
+                }
+            }
+            ContextItem::Use(ref clause) => {
+                "use".hash(hasher);
+                for name in clause.name_list.iter() {
+                    name.to_string().hash(hasher);
+                }
+            }
+            ContextItem::Context(ref clause) => {
+                "context".hash(hasher);
+                for name in clause.name_list.iter() {
+                    name.to_string().hash(hasher);
+                }
+            }
+        }
+    }
+}
+
+fn hash_interface_clause(clause: &Option<Vec<InterfaceDeclaration>>, hasher: &mut impl Hasher) {
+    match clause {
+        Some(list) => format_interface_list(list).hash(hasher),
+        None => "".hash(hasher),
+    }
+}
+
+fn hash_declaration(decl: &Declaration, hasher: &mut impl Hasher) {
+    match decl {
+        Declaration::Object(object) => {
+            format!(
+                "{:?} {} : {}",
+                object.class, object.ident, object.subtype_indication
+            )
+            .hash(hasher);
+            // The value of a constant may be used by other design units such as in a range
+            object
+                .expression
+                .as_ref()
+                .map(|expr| expr.item.to_string())
+                .hash(hasher);
+        }
+        Declaration::File(file) => {
+            format!("file {} : {}", file.ident, file.subtype_indication).hash(hasher);
+        }
+        Declaration::Type(typ) => {
+            typ.to_string().hash(hasher);
+        }
+        Declaration::Component(component) => {
+            format!(
+                "component {} generic {} port {}",
+                component.ident,
+                format_interface_list(&component.generic_list),
+                format_interface_list(&component.port_list)
+            )
+            .hash(hasher);
+        }
+        Declaration::Attribute(Attribute::Declaration(attr)) => {
+            format!("attribute {} : {}", attr.ident, attr.type_mark).hash(hasher);
+        }
+        Declaration::Attribute(Attribute::Specification(attr)) => {
+            format!("attribute {} of {}", attr.ident, attr.entity_class).hash(hasher);
+        }
+        Declaration::Alias(alias) => {
+            format!("alias {} is {}", alias.designator, alias.name).hash(hasher);
+        }
+        Declaration::SubprogramDeclaration(subprogram) => {
+            subprogram.to_string().hash(hasher);
+        }
+        Declaration::SubprogramBody(body) => {
+            body.specification.to_string().hash(hasher);
+        }
+        Declaration::SubprogramInstantiation(instance) => {
+            format!(
+                "subprogram {} is new {}",
+                instance.designator, instance.subprogram_name
+            )
+            .hash(hasher);
+        }
+        Declaration::Package(instance) => {
+            format!(
+                "package {} is new {}",
+                instance.ident, instance.package_name
+            )
+            .hash(hasher);
+        }
+        Declaration::GroupTemplate(template) => {
+            format!("group {}", template.ident).hash(hasher);
+        }
+        Declaration::Group(group) => {
+            format!("group {} : {}", group.ident, group.template_name).hash(hasher);
+        }
+        Declaration::Use(..) | Declaration::Configuration(..) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::{Code, CodeBuilder};
+
+    fn fingerprints(code: &str) -> Vec<u64> {
+        Code::new(code)
+            .design_file()
+            .design_units
+            .iter()
+            .map(interface_fingerprint)
+            .collect()
+    }
+
+    #[test]
+    fn entity_fingerprint_ignores_architecture_and_positions() {
+        let before = fingerprints(
+            "
+entity top is
+  port (clk : in bit);
+end entity;
+
+architecture rtl of top is
+  signal foo : bit;
+begin
+end architecture;
+",
+        );
+
+        let after = fingerprints(
+            "
+
+entity top is
+  port (clk   : in    bit);
+end entity;
+
+architecture rtl of top is
+  signal foo, bar : integer;
+begin
+  bar <= 0;
+end architecture;
+",
+        );
+
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn package_fingerprint_changes_with_type_definitions() {
+        let before = fingerprints(
+            "
+package pkg is
+  type state_t is (idle, busy);
+  type rec_t is record
+    a : bit;
+  end record;
+end package;
+",
+        );
+
+        let literal_added = fingerprints(
+            "
+package pkg is
+  type state_t is (idle, busy, done);
+  type rec_t is record
+    a : bit;
+  end record;
+end package;
+",
+        );
+
+        let field_changed = fingerprints(
+            "
+package pkg is
+  type state_t is (idle, busy);
+  type rec_t is record
+    a : bit_vector(0 to 1);
+  end record;
+end package;
+",
+        );
+
+        assert_ne!(before, literal_added);
+        assert_ne!(before, field_changed);
+    }
+
+    #[test]
+    fn fingerprint_does_not_depend_on_previously_parsed_code() {
+        let code = "
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity top is
+  port (clk : in std_logic);
+end entity;
+";
+        let before = fingerprints(code);
+
+        let builder = CodeBuilder::new();
+        builder
+            .code("entity other is end entity; architecture a of other is begin end;")
+            .design_file();
+        let after: Vec<_> = builder
+            .code(code)
+            .design_file()
+            .design_units
+            .iter()
+            .map(interface_fingerprint)
+            .collect();
+
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn entity_fingerprint_changes_with_ports() {
+        let before = fingerprints(
+            "
+entity top is
+  port (clk : in bit);
+end entity;
+",
+        );
+
+        let after = fingerprints(
+            "
+entity top is
+  port (clk : in bit; rst : in bit);
+end entity;
+",
+        );
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn package_fingerprint_ignores_package_body() {
+        let before = fingerprints(
+            "
+package pkg is
+  constant c : natural;
+end package;
+
+package body pkg is
+  constant c : natural := 0;
+end package body;
+",
+        );
+
+        let after = fingerprints(
+            "
+package pkg is
+  constant c : natural;
+end package;
+
+package body pkg is
+  constant c : natural := 1;
+end package body;
+",
+        );
+
+        assert_eq!(before, after);
+
+        let changed = fingerprints(
+            "
+package pkg is
+  constant c : integer;
+end package;
+",
+        );
+
+        assert_ne!(before[0], changed[0]);
+    }
+
+    #[test]
+    fn package_fingerprint_changes_with_constant_value() {
+        let before = fingerprints(
+            "
+package pkg is
+  constant c : natural := 0;
+end package;
+",
+        );
+
+        let after = fingerprints(
+            "
+package pkg is
+  constant c : natural := 1;
+end package;
+",
+        );
+
+        assert_ne!(before, after);
+    }
+}
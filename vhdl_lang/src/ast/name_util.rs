@@ -26,6 +26,15 @@ impl From<WithPos<SelectedName>> for WithPos<Name> {
     }
 }
 
+impl SelectedName {
+    pub fn suffix_reference(&self) -> Option<&SrcPos> {
+        match self {
+            SelectedName::Designator(suffix) => suffix.reference.as_ref(),
+            SelectedName::Selected(_, suffix) => suffix.item.reference.as_ref(),
+        }
+    }
+}
+
 impl WithPos<SelectedName> {
     pub fn suffix_pos(&self) -> &SrcPos {
         match self.item {
@@ -254,6 +263,14 @@ impl SubprogramDesignator {
     }
 }
 
+impl SubprogramInstantiation {
+    pub fn designator(&self) -> WithPos<Designator> {
+        self.designator
+            .clone()
+            .map_into(|des| des.into_designator())
+    }
+}
+
 impl SubprogramDeclaration {
     pub fn designator(&self) -> WithPos<Designator> {
         match self {
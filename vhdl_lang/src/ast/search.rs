@@ -66,6 +66,16 @@ pub trait Searcher {
     fn search_with_pos(&mut self, _pos: &SrcPos) -> SearchState {
         NotFinished
     }
+
+    /// Search an expression, nested expressions are searched afterwards
+    fn search_expression(&mut self, _pos: &SrcPos, _expr: &Expression) -> SearchState {
+        NotFinished
+    }
+
+    /// Search a name, prefixes and nested names are searched afterwards
+    fn search_name(&mut self, _pos: &SrcPos, _name: &Name) -> SearchState {
+        NotFinished
+    }
     fn search_source(&mut self, _source: &Source) -> SearchState {
         NotFinished
     }
@@ -467,7 +477,7 @@ impl Search for WithPos<SelectedName> {
 }
 
 fn search_pos_name(pos: &SrcPos, name: &Name, searcher: &mut impl Searcher) -> SearchResult {
-    match name {
+    searcher.search_name(pos, name).or_else(|| match name {
         Name::Selected(ref prefix, ref designator) => {
             return_if_found!(prefix.search(searcher));
             return_if_found!(designator.search(searcher));
@@ -504,7 +514,7 @@ fn search_pos_name(pos: &SrcPos, name: &Name, searcher: &mut impl Searcher) -> S
             return_if_found!(subtype.search(searcher));
             NotFound
         }
-    }
+    })
 }
 
 impl Search for WithPos<Name> {
@@ -629,6 +639,12 @@ impl Search for TypeDeclaration {
                         ProtectedTypeDeclarativeItem::Subprogram(ref subprogram) => {
                             return_if_found!(subprogram.search(searcher));
                         }
+                        ProtectedTypeDeclarativeItem::Use(ref use_clause) => {
+                            return_if_found!(searcher
+                                .search_with_pos(&use_clause.pos)
+                                .or_else(|| use_clause.item.name_list.search(searcher)));
+                        }
+                        ProtectedTypeDeclarativeItem::AttributeSpecification(..) => {}
                     }
                 }
             }
@@ -685,6 +701,7 @@ impl Search for TypeDeclaration {
 
 fn search_pos_expr(pos: &SrcPos, expr: &Expression, searcher: &mut impl Searcher) -> SearchResult {
     return_if_found!(searcher.search_with_pos(pos).or_not_found());
+    return_if_found!(searcher.search_expression(pos, expr).or_not_found());
     match expr {
         Expression::Binary(_, ref left, ref right) => {
             return_if_found!(left.search(searcher));
@@ -815,6 +832,23 @@ impl Search for Declaration {
             Declaration::SubprogramDeclaration(decl) => {
                 return_if_found!(decl.search(searcher));
             }
+            Declaration::SubprogramInstantiation(instance) => {
+                let SubprogramInstantiation {
+                    kind: _,
+                    designator,
+                    subprogram_name,
+                    signature,
+                    generic_map,
+                } = instance;
+                return_if_found!(searcher.search_decl_pos(&designator.pos).or_not_found());
+                return_if_found!(subprogram_name.search(searcher));
+                if let Some(signature) = signature {
+                    return_if_found!(signature.item.search(searcher));
+                }
+                if let Some(generic_map) = generic_map {
+                    return_if_found!(generic_map.search(searcher));
+                }
+            }
             Declaration::Attribute(Attribute::Declaration(decl)) => {
                 return_if_found!(decl.type_mark.search(searcher));
             }
@@ -858,6 +892,15 @@ impl Search for Declaration {
                 return_if_found!(open_info.search(searcher));
                 return_if_found!(file_name.search(searcher));
             }
+            Declaration::GroupTemplate(template) => {
+                return_if_found!(searcher
+                    .search_decl_pos(template.ident.pos())
+                    .or_not_found());
+            }
+            Declaration::Group(group) => {
+                return_if_found!(searcher.search_decl_pos(group.ident.pos()).or_not_found());
+                return_if_found!(group.template_name.search(searcher));
+            }
 
             // @TODO more
             _ => {}
@@ -1031,6 +1074,7 @@ impl Search for CaseStatement {
             is_matching: _,
             expression,
             alternatives,
+            end_pos: _,
         } = self;
         return_if_found!(expression.search(searcher));
         return_if_found!(search_alternatives(alternatives, false, searcher));
@@ -1146,3 +1190,159 @@ impl Searcher for FindAllReferences {
         NotFinished
     }
 }
+
+// Search for the position of all expressions
+#[derive(Default)]
+pub struct FindAllExpressions {
+    expressions: Vec<SrcPos>,
+}
+
+impl FindAllExpressions {
+    pub fn search(searchable: &impl Search) -> Vec<SrcPos> {
+        let mut searcher = Self::default();
+        let _ = searchable.search(&mut searcher);
+        searcher.expressions
+    }
+}
+
+impl Searcher for FindAllExpressions {
+    fn search_expression(&mut self, pos: &SrcPos, _expr: &Expression) -> SearchState {
+        self.expressions.push(pos.clone());
+        NotFinished
+    }
+}
+
+/// A reference to a declaration by name
+#[derive(PartialEq, Debug, Clone)]
+pub struct NameReference {
+    pub designator: Designator,
+    pub pos: SrcPos,
+    /// The position of the declaration, None when the name could not be resolved
+    pub decl_pos: Option<SrcPos>,
+}
+
+// Search for all names which reference a declaration in the order they appear
+#[derive(Default)]
+pub struct FindAllNameReferences {
+    references: Vec<NameReference>,
+}
+
+impl FindAllNameReferences {
+    pub fn search(searchable: &impl Search) -> Vec<NameReference> {
+        let mut searcher = Self::default();
+        let _ = searchable.search(&mut searcher);
+        searcher.references
+    }
+}
+
+impl Searcher for FindAllNameReferences {
+    fn search_designator_ref(
+        &mut self,
+        pos: &SrcPos,
+        designator: &WithRef<Designator>,
+    ) -> SearchState {
+        self.references.push(NameReference {
+            designator: designator.item.clone(),
+            pos: pos.clone(),
+            decl_pos: designator.reference.clone(),
+        });
+        NotFinished
+    }
+
+    fn search_ident_ref(&mut self, ident: &WithRef<Ident>) -> SearchState {
+        self.references.push(NameReference {
+            designator: Designator::Identifier(ident.item.item.clone()),
+            pos: ident.item.pos.clone(),
+            decl_pos: ident.reference.clone(),
+        });
+        NotFinished
+    }
+}
+
+// Search for selected names such as lib.pkg.name which could not be resolved,
+// the prefix of such a name is a library which has no declaration in the source code.
+// Selected names with a declared prefix such as rec.field are local references.
+#[derive(Default)]
+pub struct FindExternalReferences {
+    references: Vec<(Symbol, SrcPos)>,
+}
+
+impl FindExternalReferences {
+    pub fn search(searchable: &impl Search) -> Vec<(Symbol, SrcPos)> {
+        let mut searcher = Self::default();
+        let _ = searchable.search(&mut searcher);
+        searcher.references
+    }
+}
+
+/// The designators of a name on the form a.b.c or a.b.all
+fn selected_designators(name: &Name) -> Option<Vec<&WithRef<Designator>>> {
+    match name {
+        Name::Designator(designator) => Some(vec![designator]),
+        Name::Selected(prefix, suffix) => {
+            let mut designators = selected_designators(&prefix.item)?;
+            designators.push(&suffix.item);
+            Some(designators)
+        }
+        Name::SelectedAll(prefix) => selected_designators(&prefix.item),
+        _ => None,
+    }
+}
+
+impl Searcher for FindExternalReferences {
+    fn search_name(&mut self, pos: &SrcPos, name: &Name) -> SearchState {
+        let designators = match selected_designators(name) {
+            Some(designators) if designators.len() > 1 => designators,
+            _ => {
+                return NotFinished;
+            }
+        };
+
+        if designators[0].reference.is_none() {
+            let unresolved = designators[1..]
+                .iter()
+                .find(|designator| designator.reference.is_none());
+
+            if let Some(WithRef {
+                item: Designator::Identifier(symbol),
+                ..
+            }) = unresolved
+            {
+                self.references.push((symbol.clone(), pos.clone()));
+            }
+        }
+
+        // The prefixes are part of the same name and are not searched again
+        Finished(NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn finds_all_expressions() {
+        let code = Code::new(
+            "
+architecture a of ent is
+begin
+  sig1 <= sig2 and sig3;
+  sig4 <= fun(sig5);
+end architecture;
+",
+        );
+        let design_file = code.design_file();
+        assert_eq!(
+            FindAllExpressions::search(&design_file.design_units[0]),
+            vec![
+                code.s1("sig2 and sig3").pos(),
+                code.s1("sig2").pos(),
+                code.s1("sig3").pos(),
+                code.s1("fun(sig5)").pos(),
+                code.s1("sig5").pos(),
+            ]
+        );
+    }
+}
@@ -7,6 +7,8 @@
 
 mod contents;
 mod diagnostic;
+#[cfg(feature = "json")]
+mod json;
 mod latin_1;
 mod message;
 mod source;
@@ -14,6 +16,8 @@ mod symbol_table;
 
 pub use contents::*;
 pub use diagnostic::*;
+#[cfg(feature = "json")]
+pub use json::*;
 pub use latin_1::*;
 pub use message::*;
 pub use source::*;
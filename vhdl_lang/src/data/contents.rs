@@ -11,6 +11,8 @@ use std::io;
 use std::io::prelude::Read;
 use std::path::Path;
 
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
 pub struct Contents {
     lines: Vec<String>,
 }
@@ -20,12 +22,26 @@ impl Contents {
         let mut file = File::open(file_name)?;
         let mut bytes = Vec::new();
         file.read_to_end(&mut bytes)?;
-        Ok(Contents::from_str(
-            &Latin1String::from_vec(bytes).to_string(),
-        ))
+        Ok(Contents::from_bytes(bytes))
+    }
+
+    /// Files starting with an UTF-8 byte order mark are decoded as UTF-8 where
+    /// invalid sequences are replaced, all other files are decoded as Latin-1
+    fn from_bytes(bytes: Vec<u8>) -> Contents {
+        if bytes.starts_with(UTF8_BOM) {
+            Contents::from_str(&String::from_utf8_lossy(&bytes[UTF8_BOM.len()..]))
+        } else {
+            Contents::from_str(&Latin1String::from_vec(bytes).to_string())
+        }
     }
 
     pub fn from_str(code: &str) -> Contents {
+        // The byte order mark is not part of the code and must not offset positions
+        let code = if code.starts_with('\u{feff}') {
+            &code['\u{feff}'.len_utf8()..]
+        } else {
+            code
+        };
         Contents {
             lines: split_lines(code),
         }
@@ -347,6 +363,31 @@ mod tests {
         ContentReader::new(contents)
     }
 
+    #[test]
+    fn from_str_strips_byte_order_mark() {
+        let contents = new("\u{feff}hi");
+        assert_eq!(contents.get_line(0), Some("hi"));
+        assert_eq!(contents.end(), Position::new(0, 2));
+    }
+
+    #[test]
+    fn from_bytes_with_byte_order_mark_is_utf8() {
+        let contents = Contents::from_bytes(b"\xEF\xBB\xBFh\xC3\xA5".to_vec());
+        assert_eq!(contents.get_line(0), Some("hå"));
+    }
+
+    #[test]
+    fn from_bytes_with_byte_order_mark_replaces_invalid_utf8() {
+        let contents = Contents::from_bytes(b"\xEF\xBB\xBFh\xE5i".to_vec());
+        assert_eq!(contents.get_line(0), Some("h\u{fffd}i"));
+    }
+
+    #[test]
+    fn from_bytes_without_byte_order_mark_is_latin1() {
+        let contents = Contents::from_bytes(b"h\xE5i".to_vec());
+        assert_eq!(contents.get_line(0), Some("håi"));
+    }
+
     #[test]
     fn pop_latin1_ok() {
         let contents = new("hi");
@@ -4,10 +4,11 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::SrcPos;
+use super::{Source, SrcPos};
+use fnv::FnvHashSet;
 use std::convert::{AsRef, Into};
 
-#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
 pub enum Severity {
     Hint,
     Info,
@@ -15,6 +16,32 @@ pub enum Severity {
     Error,
 }
 
+impl AsRef<str> for Severity {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+            Self::Hint => "hint",
+        }
+    }
+}
+
+/// Distinguishes source code which could not be parsed
+/// from source code which was parsed but is semantically wrong
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+pub enum Origin {
+    Syntax,
+    Semantic,
+}
+
+/// A suggested edit which replaces the text at the position, an empty range is an insertion
+#[derive(PartialEq, Debug, Clone, Eq, Hash)]
+pub struct TextEdit {
+    pub pos: SrcPos,
+    pub new_text: String,
+}
+
 #[must_use]
 #[derive(PartialEq, Debug, Clone, Eq, Hash)]
 pub struct Diagnostic {
@@ -22,6 +49,16 @@ pub struct Diagnostic {
     pub message: String,
     pub severity: Severity,
     pub related: Vec<(SrcPos, String)>,
+    /// Optional category such as "naming" used to group diagnostics when reporting
+    pub category: Option<&'static str>,
+    /// Optional code such as "duplicate-declaration" identifying the rule which was violated
+    pub code: Option<&'static str>,
+    /// Set explicitly or by the handler the diagnostic is pushed to, semantic when unset
+    origin: Option<Origin>,
+    /// Optional edits which would resolve the diagnostic
+    /// Boxed to keep the diagnostic small since it is the error type of most results
+    #[allow(clippy::box_collection)]
+    fixits: Option<Box<Vec<TextEdit>>>,
 }
 
 impl Diagnostic {
@@ -31,6 +68,10 @@ impl Diagnostic {
             message: msg.into(),
             severity,
             related: vec![],
+            category: None,
+            code: None,
+            origin: None,
+            fixits: None,
         }
     }
 
@@ -56,9 +97,67 @@ impl Diagnostic {
             pos: self.pos,
             severity: self.severity,
             related: vec![],
+            category: self.category,
+            code: self.code,
+            origin: self.origin,
+            fixits: self.fixits,
         }
     }
 
+    pub fn with_category(self, category: &'static str) -> Diagnostic {
+        Diagnostic {
+            category: Some(category),
+            ..self
+        }
+    }
+
+    pub fn with_code(self, code: &'static str) -> Diagnostic {
+        Diagnostic {
+            code: Some(code),
+            ..self
+        }
+    }
+
+    pub fn with_origin(self, origin: Origin) -> Diagnostic {
+        Diagnostic {
+            origin: Some(origin),
+            ..self
+        }
+    }
+
+    /// Set the origin unless it was already set explicitly
+    pub(crate) fn with_default_origin(self, origin: Origin) -> Diagnostic {
+        Diagnostic {
+            origin: self.origin.or(Some(origin)),
+            ..self
+        }
+    }
+
+    pub fn origin(&self) -> Origin {
+        self.origin.unwrap_or(Origin::Semantic)
+    }
+
+    pub fn with_fixit(self, pos: impl AsRef<SrcPos>, new_text: impl Into<String>) -> Diagnostic {
+        let mut diagnostic = self;
+        diagnostic
+            .fixits
+            .get_or_insert_with(Default::default)
+            .push(TextEdit {
+                pos: pos.as_ref().clone(),
+                new_text: new_text.into(),
+            });
+        diagnostic
+    }
+
+    /// Suggested edits which would resolve the diagnostic
+    pub fn fixits(&self) -> &[TextEdit] {
+        self.fixits.as_ref().map_or(&[], |fixits| fixits.as_slice())
+    }
+
+    pub fn is_syntax(&self) -> bool {
+        self.origin() == Origin::Syntax
+    }
+
     pub fn related(self, item: impl AsRef<SrcPos>, message: impl Into<String>) -> Diagnostic {
         let mut diagnostic = self;
         diagnostic.add_related(item, message);
@@ -89,19 +188,304 @@ impl Diagnostic {
             result.push_str(&pos.show(&format!("related: {}", message)));
             result.push('\n');
         }
-        let severity = match self.severity {
-            Severity::Error => &"error",
-            Severity::Warning => &"warning",
-            Severity::Info => &"info",
-            Severity::Hint => &"hint",
+        result.push_str(
+            &self
+                .pos
+                .show(&format!("{}: {}", self.severity.as_ref(), self.message)),
+        );
+        self.push_parseable_fixits(&mut result);
+        result
+    }
+
+    /// Render the diagnostic in a rustc like style for terminal output
+    /// with the offending source line underlined by carets and indented related positions
+    pub fn render(&self) -> String {
+        let mut result = String::new();
+        render_pos(
+            &mut result,
+            "",
+            &format!("{}: {}", self.severity.as_ref(), self.message),
+            &self.pos,
+        );
+        for (pos, message) in self.related.iter() {
+            render_pos(&mut result, "    ", &format!("note: {}", message), pos);
+        }
+        self.push_parseable_fixits(&mut result);
+        result
+    }
+
+    /// One line per suggested edit in the style of -fdiagnostics-parseable-fixits of GCC and Clang
+    /// such as 'fix-it:"pkg.vhd":{3:5-3:6}:"b"' with one based lines and columns
+    /// where the end column is exclusive
+    fn push_parseable_fixits(&self, into: &mut String) {
+        for fixit in self.fixits() {
+            let range = fixit.pos.range();
+            into.push_str(&format!(
+                "fix-it:\"{}\":{{{}:{}-{}:{}}}:\"{}\"\n",
+                escape_fixit_string(&fixit.pos.file_name().to_string_lossy()),
+                range.start.line + 1,
+                range.start.character + 1,
+                range.end.line + 1,
+                range.end.character + 1,
+                escape_fixit_string(&fixit.new_text)
+            ));
+        }
+    }
+
+    /// A deterministic single line representation for snapshot testing such as
+    /// 'duplicate-declaration error 3:3-3:4 Duplicate declaration of 'a' [related 2:3-2:4 Previously defined here]'
+    /// Positions within `source` omit the file name, related notes are sorted by position
+    /// and are followed by the suggested edits
+    pub fn canonical_string(&self, source: &Source) -> String {
+        let pos_string = |pos: &SrcPos| {
+            let range = pos.range();
+            let location = format!(
+                "{}:{}-{}:{}",
+                range.start.line + 1,
+                range.start.character + 1,
+                range.end.line + 1,
+                range.end.character + 1
+            );
+            if &pos.source == source {
+                location
+            } else {
+                format!("{}:{}", pos.file_name().to_string_lossy(), location)
+            }
         };
-        result.push_str(&self.pos.show(&format!("{}: {}", severity, self.message)));
+        let single_line = |message: &str| message.replace('\n', "\\n");
+
+        let mut result = format!(
+            "{} {} {} {}",
+            self.code.unwrap_or("-"),
+            self.severity.as_ref(),
+            pos_string(&self.pos),
+            single_line(&self.message)
+        );
+
+        let mut related: Vec<_> = self.related.iter().collect();
+        related.sort_by(|(pos_a, msg_a), (pos_b, msg_b)| {
+            (
+                pos_a.file_name(),
+                pos_a.range().start,
+                pos_a.range().end,
+                msg_a,
+            )
+                .cmp(&(
+                    pos_b.file_name(),
+                    pos_b.range().start,
+                    pos_b.range().end,
+                    msg_b,
+                ))
+        });
+        for (pos, message) in related {
+            result.push_str(&format!(
+                " [related {} {}]",
+                pos_string(pos),
+                single_line(message)
+            ));
+        }
+        for fixit in self.fixits() {
+            result.push_str(&format!(
+                " [fixit {} '{}']",
+                pos_string(&fixit.pos),
+                single_line(&fixit.new_text)
+            ));
+        }
         result
     }
 }
 
+/// Escape backslashes, quotes and line breaks such that the string fits within quotes on one line
+fn escape_fixit_string(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    for chr in string.chars() {
+        match chr {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            _ => result.push(chr),
+        }
+    }
+    result
+}
+
+fn render_pos(into: &mut String, indent: &str, header: &str, pos: &SrcPos) {
+    let (line, column) = pos.line_column();
+    into.push_str(indent);
+    into.push_str(header);
+    into.push('\n');
+    into.push_str(&format!(
+        "{} --> {}:{}:{}\n",
+        indent,
+        pos.file_name().to_string_lossy(),
+        line,
+        column
+    ));
+    for snippet_line in pos.caret_snippet().lines() {
+        into.push_str(indent);
+        into.push_str(snippet_line);
+        into.push('\n');
+    }
+}
+
 pub type DiagnosticResult<T> = Result<T, Diagnostic>;
 
+/// Remove diagnostics which repeat an earlier one with the same severity, position, message and related notes
+/// The first occurrence of each diagnostic keeps its place in the order
+// The source of a position is hashed by its immutable file id
+#[allow(clippy::mutable_key_type)]
+pub fn dedup(diagnostics: &mut Vec<Diagnostic>) {
+    let mut seen = FnvHashSet::default();
+    let keep: Vec<bool> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            seen.insert((
+                diagnostic.severity,
+                &diagnostic.pos,
+                &diagnostic.message,
+                &diagnostic.related,
+            ))
+        })
+        .collect();
+    let mut keep = keep.into_iter();
+    diagnostics.retain(|_| keep.next().unwrap_or(true));
+}
+
+/// Order diagnostics by the file name and start of their position
+/// Diagnostics at the same position keep their relative order
+pub fn sort_by_position(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by(|a, b| {
+        (a.pos.file_name(), a.pos.start()).cmp(&(b.pos.file_name(), b.pos.start()))
+    });
+}
+
+/// Summarize the number of diagnostics of each severity such as '3 errors, 1 warning, 0 hints'
+pub fn summary(diagnostics: &[Diagnostic]) -> String {
+    let count = |severity: Severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+
+    let plural = |num: usize, noun: &str| {
+        if num == 1 {
+            format!("{} {}", num, noun)
+        } else {
+            format!("{} {}s", num, noun)
+        }
+    };
+
+    format!(
+        "{}, {}, {}",
+        plural(count(Severity::Error), "error"),
+        plural(count(Severity::Warning), "warning"),
+        plural(count(Severity::Hint), "hint")
+    )
+}
+
+/// The diagnostics with the given code
+pub fn messages_with_code<'a>(messages: &'a [Diagnostic], code: &str) -> Vec<&'a Diagnostic> {
+    messages
+        .iter()
+        .filter(|diagnostic| diagnostic.code == Some(code))
+        .collect()
+}
+
+/// The homographs reported by an analysis as the name with the position of the earlier
+/// and the later declaration, taken from the diagnostics with the code 'duplicate-declaration'
+pub fn find_homographs(diagnostics: &[Diagnostic]) -> Vec<(String, SrcPos, SrcPos)> {
+    messages_with_code(diagnostics, "duplicate-declaration")
+        .into_iter()
+        .filter_map(|diagnostic| {
+            let (prev_pos, _) = diagnostic.related.first()?;
+            Some((
+                source_text(&diagnostic.pos),
+                prev_pos.clone(),
+                diagnostic.pos.clone(),
+            ))
+        })
+        .collect()
+}
+
+/// The text of a position within a single line
+fn source_text(pos: &SrcPos) -> String {
+    let range = pos.range();
+    let contents = pos.source.contents();
+    contents
+        .get_line(range.start.line as usize)
+        .map(|line| {
+            line.chars()
+                .skip(range.start.character as usize)
+                .take(range.end.character.saturating_sub(range.start.character) as usize)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The distinct codes of the diagnostics, such as for building a filter
+pub fn codes_present(messages: &[Diagnostic]) -> FnvHashSet<&str> {
+    messages
+        .iter()
+        .filter_map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+/// Diagnostics sharing the same primary position presented as one entry
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupedDiagnostic {
+    pub pos: SrcPos,
+    /// The individual diagnostics in the order they were reported
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl GroupedDiagnostic {
+    /// The most severe severity of the grouped diagnostics
+    pub fn severity(&self) -> Severity {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.severity)
+            .max()
+            .unwrap_or(Severity::Hint)
+    }
+
+    /// The codes of the grouped diagnostics in the order they were reported
+    pub fn codes(&self) -> Vec<&'static str> {
+        self.diagnostics
+            .iter()
+            .filter_map(|diagnostic| diagnostic.code)
+            .collect()
+    }
+
+    /// The messages of the grouped diagnostics with one message per line
+    pub fn message(&self) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Group diagnostics by their primary position for a cleaner display in editors
+/// The groups are ordered by the first diagnostic reported at each position
+pub fn group_by_position(diagnostics: Vec<Diagnostic>) -> Vec<GroupedDiagnostic> {
+    let mut groups: Vec<GroupedDiagnostic> = Vec::new();
+
+    for diagnostic in diagnostics.into_iter() {
+        if let Some(group) = groups.iter_mut().find(|group| group.pos == diagnostic.pos) {
+            group.diagnostics.push(diagnostic);
+        } else {
+            groups.push(GroupedDiagnostic {
+                pos: diagnostic.pos.clone(),
+                diagnostics: vec![diagnostic],
+            });
+        }
+    }
+
+    groups
+}
+
 pub trait DiagnosticHandler {
     fn push(self: &mut Self, diagnostic: Diagnostic);
     fn append(self: &mut Self, diagnostics: Vec<Diagnostic>) {
@@ -147,6 +531,85 @@ impl DiagnosticHandler for Vec<Diagnostic> {
     }
 }
 
+/// Lowers errors with one of the downgraded codes to warnings before forwarding them,
+/// such as to tolerate a rule temporarily while migrating a code base
+///
+/// [`Project::set_downgraded_codes`](crate::Project::set_downgraded_codes) applies this to
+/// the result of an analysis, otherwise diagnostics may be pushed through it directly.
+pub struct DowngradedDiagnostics<'a> {
+    codes: FnvHashSet<String>,
+    diagnostics: &'a mut dyn DiagnosticHandler,
+}
+
+impl<'a> DowngradedDiagnostics<'a> {
+    pub fn new(
+        codes: impl IntoIterator<Item = impl Into<String>>,
+        diagnostics: &'a mut dyn DiagnosticHandler,
+    ) -> DowngradedDiagnostics<'a> {
+        DowngradedDiagnostics {
+            codes: codes.into_iter().map(|code| code.into()).collect(),
+            diagnostics,
+        }
+    }
+}
+
+impl<'a> DiagnosticHandler for DowngradedDiagnostics<'a> {
+    fn push(&mut self, mut diagnostic: Diagnostic) {
+        if diagnostic.severity == Severity::Error {
+            if let Some(code) = diagnostic.code {
+                if self.codes.contains(code) {
+                    diagnostic.severity = Severity::Warning;
+                }
+            }
+        }
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+/// The severity, position, message and related notes of a diagnostic
+type DiagnosticIdentity = (Severity, SrcPos, String, Vec<(SrcPos, String)>);
+
+/// Forwards each diagnostic to a callback as soon as it is pushed,
+/// such as to report diagnostics while a long analysis is still running
+///
+/// Diagnostics below the minimum severity are dropped as well as diagnostics which repeat
+/// an earlier one with the same severity, position, message and related notes.
+pub struct DiagnosticCallback<F: FnMut(Diagnostic)> {
+    min_severity: Severity,
+    seen: FnvHashSet<DiagnosticIdentity>,
+    callback: F,
+}
+
+impl<F: FnMut(Diagnostic)> DiagnosticCallback<F> {
+    pub fn new(min_severity: Severity, callback: F) -> DiagnosticCallback<F> {
+        DiagnosticCallback {
+            min_severity,
+            seen: FnvHashSet::default(),
+            callback,
+        }
+    }
+}
+
+impl<F: FnMut(Diagnostic)> DiagnosticHandler for DiagnosticCallback<F> {
+    // The source of a position is hashed by its immutable file id
+    #[allow(clippy::mutable_key_type)]
+    fn push(&mut self, diagnostic: Diagnostic) {
+        if diagnostic.severity < self.min_severity {
+            return;
+        }
+
+        let key = (
+            diagnostic.severity,
+            diagnostic.pos.clone(),
+            diagnostic.message.clone(),
+            diagnostic.related.clone(),
+        );
+        if self.seen.insert(key) {
+            (self.callback)(diagnostic);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +650,123 @@ error: Greetings
         );
     }
 
+    #[test]
+    fn dedup_removes_identical_diagnostics() {
+        let code = Code::new("hello\nworld\nline\n");
+        let mut diagnostics = vec![
+            Diagnostic::error(code.s1("world"), "Greetings"),
+            Diagnostic::error(code.s1("hello"), "Greetings"),
+            Diagnostic::error(code.s1("world"), "Greetings"),
+        ];
+        dedup(&mut diagnostics);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(code.s1("world"), "Greetings"),
+                Diagnostic::error(code.s1("hello"), "Greetings"),
+            ]
+        );
+    }
+
+    #[test]
+    fn dedup_keeps_diagnostics_with_different_related() {
+        let code = Code::new("hello\nworld\nline\n");
+        let mut diagnostics = vec![
+            Diagnostic::error(code.s1("world"), "Greetings").related(code.s1("hello"), "From here"),
+            Diagnostic::error(code.s1("world"), "Greetings").related(code.s1("line"), "From here"),
+        ];
+        let expected = diagnostics.clone();
+        dedup(&mut diagnostics);
+        assert_eq!(diagnostics, expected);
+    }
+
+    #[test]
+    fn sort_diagnostics_by_position() {
+        let code_a = Code::new_with_file_name(Path::new("a.vhd"), "hello\nworld\n");
+        let code_b = Code::new_with_file_name(Path::new("b.vhd"), "line\n");
+        let mut diagnostics = vec![
+            Diagnostic::error(code_b.s1("line"), "Greetings"),
+            Diagnostic::error(code_a.s1("world"), "First"),
+            Diagnostic::error(code_a.s1("hello"), "Greetings"),
+            Diagnostic::warning(code_a.s1("world"), "Second"),
+        ];
+        sort_by_position(&mut diagnostics);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error(code_a.s1("hello"), "Greetings"),
+                Diagnostic::error(code_a.s1("world"), "First"),
+                Diagnostic::warning(code_a.s1("world"), "Second"),
+                Diagnostic::error(code_b.s1("line"), "Greetings"),
+            ]
+        );
+    }
+
+    #[test]
+    fn downgrades_errors_with_code_to_warnings() {
+        let code = Code::new("hello\nworld\n");
+        let mut diagnostics = Vec::new();
+        {
+            let mut downgraded =
+                DowngradedDiagnostics::new(vec!["duplicate-declaration"], &mut diagnostics);
+            downgraded.push(
+                Diagnostic::error(code.s1("hello"), "Duplicate declaration of 'hello'")
+                    .with_code("duplicate-declaration"),
+            );
+            downgraded.push(
+                Diagnostic::error(code.s1("world"), "No declaration of 'world'")
+                    .with_code("unresolved"),
+            );
+        }
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::warning(code.s1("hello"), "Duplicate declaration of 'hello'")
+                    .with_code("duplicate-declaration"),
+                Diagnostic::error(code.s1("world"), "No declaration of 'world'")
+                    .with_code("unresolved"),
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_of_mixed_severities() {
+        let code = Code::new("hello\nworld\nline\n");
+        let diagnostics = vec![
+            Diagnostic::error(code.s1("hello"), "Greetings"),
+            Diagnostic::error(code.s1("world"), "Greetings"),
+            Diagnostic::warning(code.s1("line"), "Greetings"),
+            Diagnostic::error(code.s1("line"), "Greetings"),
+        ];
+        assert_eq!(summary(&diagnostics), "3 errors, 1 warning, 0 hints");
+        assert_eq!(summary(&[]), "0 errors, 0 warnings, 0 hints");
+    }
+
+    #[test]
+    fn group_diagnostics_at_same_position() {
+        let code = Code::new("signal foo : bit;\nsignal bar : bit;");
+        let diagnostics = vec![
+            Diagnostic::hint(code.s1("foo"), "Unused signal 'foo'").with_code("unused"),
+            Diagnostic::warning(code.s1("bar"), "Unused signal 'bar'").with_code("unused"),
+            Diagnostic::warning(code.s1("foo"), "Signal 'foo' shadows 'foo'")
+                .with_code("shadowing"),
+        ];
+
+        let groups = group_by_position(diagnostics);
+        assert_eq!(groups.len(), 2);
+
+        assert_eq!(groups[0].pos, code.s1("foo").pos());
+        assert_eq!(groups[0].codes(), vec!["unused", "shadowing"]);
+        assert_eq!(groups[0].severity(), Severity::Warning);
+        assert_eq!(
+            groups[0].message(),
+            "Unused signal 'foo'\nSignal 'foo' shadows 'foo'"
+        );
+
+        assert_eq!(groups[1].pos, code.s1("bar").pos());
+        assert_eq!(groups[1].codes(), vec!["unused"]);
+    }
+
     #[test]
     fn show_related() {
         let code = Code::new_with_file_name(Path::new("{unknown file}"), "hello\nworld\nline\n");
@@ -212,6 +792,74 @@ error: Greetings
 2  |  world
 3 --> line
    |  ~~~~
+"
+        );
+    }
+
+    #[test]
+    fn render_duplicate_declaration() {
+        let code = Code::new_with_file_name(
+            Path::new("pkg.vhd"),
+            "package pkg is
+  type t is (v);
+  constant a : t := v;
+\tconstant a : t := v;
+end package;",
+        );
+        let diagnostic =
+            Diagnostic::error(code.s("a :", 2).s1("a"), "Duplicate declaration of 'a'")
+                .related(code.s("a :", 1).s1("a"), "Previously defined here");
+        assert_eq!(
+            diagnostic.render(),
+            "\
+error: Duplicate declaration of 'a'
+ --> pkg.vhd:4:11
+  |
+4 |     constant a : t := v;
+  |              ^
+    note: Previously defined here
+     --> pkg.vhd:3:12
+      |
+    3 |   constant a : t := v;
+      |            ^
+"
+        );
+    }
+
+    #[test]
+    fn show_and_render_parseable_fixits() {
+        let code = Code::new_with_file_name(Path::new("pkg.vhd"), "case sel is\nend case;\n");
+        let end = code.s1("end").pos();
+        let diagnostic = Diagnostic::error(code.s1("case"), "Missing choice 'others'")
+            .with_fixit(
+                end.source.pos(end.start(), end.start()),
+                "when others => null;\n",
+            )
+            .with_fixit(code.s1("sel"), "\"sel\"\\");
+
+        assert_eq!(
+            diagnostic.show(),
+            "\
+error: Missing choice 'others'
+  --> pkg.vhd:1
+   |
+1 --> case sel is
+   |  ~~~~
+2  |  end case;
+fix-it:\"pkg.vhd\":{2:1-2:1}:\"when others => null;\\n\"
+fix-it:\"pkg.vhd\":{1:6-1:9}:\"\\\"sel\\\"\\\\\"
+"
+        );
+        assert_eq!(
+            diagnostic.render(),
+            "\
+error: Missing choice 'others'
+ --> pkg.vhd:1:1
+  |
+1 | case sel is
+  | ^^^^
+fix-it:\"pkg.vhd\":{2:1-2:1}:\"when others => null;\\n\"
+fix-it:\"pkg.vhd\":{1:6-1:9}:\"\\\"sel\\\"\\\\\"
 "
         );
     }
@@ -0,0 +1,88 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{Diagnostic, SrcPos};
+use serde_json::{json, Value};
+
+/// Serialize diagnostics as a JSON array for machine readable output such as in CI pipelines
+pub fn diagnostics_to_json(diagnostics: &[Diagnostic]) -> String {
+    Value::Array(diagnostics.iter().map(Diagnostic::to_json_value).collect()).to_string()
+}
+
+impl Diagnostic {
+    /// Serialize the diagnostic as a JSON object, positions use 1-based lines and columns
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    fn to_json_value(&self) -> Value {
+        json!({
+            "severity": self.severity.as_ref(),
+            "code": self.code,
+            "category": self.category,
+            "message": self.message,
+            "pos": pos_to_json(&self.pos),
+            "related": self.related.iter().map(|(pos, message)| json!({
+                "pos": pos_to_json(pos),
+                "message": message,
+            })).collect::<Vec<_>>(),
+            "fixits": self.fixits().iter().map(|fixit| json!({
+                "pos": pos_to_json(&fixit.pos),
+                "new_text": fixit.new_text,
+            })).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn pos_to_json(pos: &SrcPos) -> Value {
+    let (line, column) = pos.line_column();
+    let (end_line, end_column) = pos.end_line_column();
+    json!({
+        "file": pos.file_name().to_string_lossy(),
+        "line": line,
+        "column": column,
+        "end_line": end_line,
+        "end_column": end_column,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn json_has_one_based_positions() {
+        let code = Code::new(
+            "
+package pkg is
+  constant a : natural := 0;
+  constant a : natural := 0;
+end package;
+",
+        );
+        let diagnostic =
+            Diagnostic::error(code.s("a :", 2).s1("a"), "Duplicate declaration of 'a'")
+                .with_code("duplicate-declaration")
+                .related(code.s("a :", 1).s1("a"), "Previously defined here");
+
+        let json = diagnostics_to_json(&[diagnostic]);
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let diagnostic = &value[0];
+        assert_eq!(diagnostic["severity"], "error");
+        assert_eq!(diagnostic["code"], "duplicate-declaration");
+        assert_eq!(diagnostic["message"], "Duplicate declaration of 'a'");
+        assert_eq!(diagnostic["pos"]["line"], 4);
+        assert_eq!(diagnostic["pos"]["column"], 12);
+        assert_eq!(diagnostic["pos"]["end_column"], 13);
+        assert_eq!(diagnostic["related"][0]["pos"]["line"], 3);
+        assert_eq!(diagnostic["related"][0]["pos"]["column"], 12);
+        assert_eq!(
+            diagnostic["related"][0]["message"],
+            "Previously defined here"
+        );
+    }
+}
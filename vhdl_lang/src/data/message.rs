@@ -97,3 +97,52 @@ impl MessageHandler for MessagePrinter {
         println!("{}", message);
     }
 }
+
+/// Forwards each message to a callback as soon as it is pushed
+pub struct MessageCallback<F: FnMut(Message)> {
+    callback: F,
+}
+
+impl<F: FnMut(Message)> MessageCallback<F> {
+    pub fn new(callback: F) -> MessageCallback<F> {
+        MessageCallback { callback }
+    }
+}
+
+impl<F: FnMut(Message)> MessageHandler for MessageCallback<F> {
+    fn push(&mut self, message: Message) {
+        (self.callback)(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn callback_is_called_once_per_message_in_order() {
+        let mut received = Vec::new();
+        {
+            let mut handler = MessageCallback::new(|message| received.push(message));
+            handler.push(Message::warning("first"));
+            handler.push(Message::error("second"));
+        }
+        assert_eq!(
+            received,
+            vec![Message::warning("first"), Message::error("second")]
+        );
+    }
+
+    #[test]
+    fn vec_handler_keeps_message_type() {
+        let mut messages: Vec<Message> = Vec::new();
+        MessageHandler::push(&mut messages, Message::warning("first"));
+        MessageHandler::push(&mut messages, Message::error("second"));
+
+        let types: Vec<_> = messages
+            .iter()
+            .map(|message| &message.message_type)
+            .collect();
+        assert_eq!(types, vec![&MessageType::Warning, &MessageType::Error]);
+    }
+}
@@ -471,6 +471,48 @@ impl SrcPos {
         result
     }
 
+    /// Create a rustc style snippet of the first line of the position with a caret underline
+    /// Tabs are expanded to the same width in the source line and the underline
+    pub fn caret_snippet(&self) -> String {
+        let contents = self.source.contents();
+        let lineno = self.range.start.line;
+        let line = contents.get_line(lineno as usize).unwrap_or("");
+        let line = line.trim_end_matches(&['\n', '\r'][..]);
+        let lineno_str = (lineno + 1).to_string();
+
+        let mut code = String::new();
+        let mut carets = String::new();
+        let mut pos = Position {
+            line: lineno,
+            character: 0,
+        };
+        for chr in line.chars() {
+            let width = Self::visual_width(chr);
+            if chr == '\t' {
+                Self::push_replicate(&mut code, ' ', width);
+            } else {
+                code.push(chr);
+            }
+            if pos < self.range.start {
+                Self::push_replicate(&mut carets, ' ', width);
+            } else if pos < self.range.end {
+                Self::push_replicate(&mut carets, '^', width);
+            }
+            pos.character += chr.len_utf16() as u32;
+        }
+        if carets.trim().is_empty() {
+            // Empty position or position at end of line
+            carets.push('^');
+        }
+
+        let mut result = String::new();
+        let gutter = " ".repeat(lineno_str.len());
+        writeln!(result, "{} |", gutter).unwrap();
+        writeln!(result, "{} | {}", lineno_str, code.trim_end()).unwrap();
+        writeln!(result, "{} | {}", gutter, carets.trim_end()).unwrap();
+        result
+    }
+
     /// Combines two lexical positions into a larger lexical position overlapping both.
     /// The file name is assumed to be the same.
     pub fn combine_into(self, other: &dyn AsRef<Self>) -> Self {
@@ -498,6 +540,34 @@ impl SrcPos {
         self.range
     }
 
+    /// The 1-based line and column of the start of the position
+    /// The column counts characters rather than the UTF-16 code units of `Position`
+    pub fn line_column(&self) -> (usize, usize) {
+        self.line_column_of(self.range.start)
+    }
+
+    /// The 1-based line and column of the end of the position
+    pub fn end_line_column(&self) -> (usize, usize) {
+        self.line_column_of(self.range.end)
+    }
+
+    fn line_column_of(&self, pos: Position) -> (usize, usize) {
+        let character = pos.character as usize;
+        let column = match self.source.contents().get_line(pos.line as usize) {
+            Some(line) => {
+                let mut code_units = 0;
+                line.chars()
+                    .take_while(|chr| {
+                        code_units += chr.len_utf16();
+                        code_units <= character
+                    })
+                    .count()
+            }
+            None => character,
+        };
+        (pos.line as usize + 1, column + 1)
+    }
+
     pub fn file_name(&self) -> &Path {
         self.source.file_name()
     }
@@ -545,6 +615,28 @@ mod tests {
     use crate::syntax::test::{Code, CodeBuilder};
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn line_column_on_first_line() {
+        let code = Code::new("hello world");
+        assert_eq!(code.s1("world").pos().line_column(), (1, 7));
+        assert_eq!(code.s1("world").pos().end_line_column(), (1, 12));
+    }
+
+    #[test]
+    fn line_column_after_crlf() {
+        let code = Code::new("hello\r\nworld");
+        assert_eq!(code.s1("world").pos().line_column(), (2, 1));
+    }
+
+    #[test]
+    fn line_column_counts_characters_after_multibyte_character() {
+        // The emoji is two UTF-16 code units and four UTF-8 bytes
+        let code = Code::new("-- \u{1F600} world");
+        assert_eq!(code.s1("world").pos().line_column(), (1, 6));
+        let code = Code::new("-- \u{e5} world");
+        assert_eq!(code.s1("world").pos().line_column(), (1, 6));
+    }
+
     #[test]
     fn srcpos_combine() {
         let code = Code::new("hello world");
@@ -585,6 +677,18 @@ mod tests {
         });
     }
 
+    #[test]
+    fn byte_order_mark_does_not_offset_positions() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let file_name = file.path().to_owned();
+        file.write_all(b"\xEF\xBB\xBFhello\nworld\n").unwrap();
+        let code =
+            CodeBuilder::new().code_from_source(Source::from_latin1_file(&file_name).unwrap());
+        assert_eq!(code.s1("hello").start(), Position::new(0, 0));
+        assert_eq!(code.s1("world").start(), Position::new(1, 0));
+    }
+
     #[test]
     fn code_context_pos_last_line_without_newline() {
         let code = Code::new("hello world");
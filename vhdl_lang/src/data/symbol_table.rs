@@ -4,7 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::latin_1::Latin1String;
+use super::latin_1::{char_to_latin1, Latin1String};
 use parking_lot::RwLock;
 use std::sync::Arc;
 
@@ -67,6 +67,45 @@ impl std::hash::Hash for Symbol {
     }
 }
 
+/// An identifier normalized the same way as symbols are compared.
+///
+/// Basic identifiers are case-folded while extended identifiers are kept verbatim.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NormalizedIdent(String);
+
+impl NormalizedIdent {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for NormalizedIdent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Normalizes an identifier as written in VHDL source, extended identifiers include the backslashes.
+pub fn normalize_identifier(name: &str) -> NormalizedIdent {
+    if name.starts_with('\\') {
+        return NormalizedIdent(name.to_owned());
+    }
+
+    NormalizedIdent(
+        name.chars()
+            .map(|chr| match char_to_latin1(chr) {
+                Some(byte) => Latin1String::lowercase(byte) as char,
+                None => chr,
+            })
+            .collect(),
+    )
+}
+
+/// Returns true if two identifiers denote the same symbol.
+pub fn identifiers_equal(left: &str, right: &str) -> bool {
+    normalize_identifier(left) == normalize_identifier(right)
+}
+
 /// A thread-safe symbol table to keep track of identifiers.
 ///
 /// This table maintains a mapping from symbol strings to
@@ -239,4 +278,33 @@ mod tests {
         let sym1 = symtab.insert_utf8("abc");
         assert_ne!(sym0, sym1);
     }
+
+    #[test]
+    fn basic_identifiers_normalize_case_insensitive() {
+        assert_eq!(normalize_identifier("Foo"), normalize_identifier("foo"));
+        assert_eq!(normalize_identifier("FOO").as_str(), "foo");
+        assert_eq!(normalize_identifier("\u{c4}bc").as_str(), "\u{e4}bc");
+        assert!(identifiers_equal("Foo", "fOO"));
+        assert!(!identifiers_equal("foo", "bar"));
+    }
+
+    #[test]
+    fn extended_identifiers_normalize_case_sensitive() {
+        assert_ne!(
+            normalize_identifier("\\Foo\\"),
+            normalize_identifier("\\foo\\")
+        );
+        assert_eq!(normalize_identifier("\\Foo\\").as_str(), "\\Foo\\");
+        assert!(!identifiers_equal("\\Foo\\", "\\foo\\"));
+        assert!(!identifiers_equal("\\foo\\", "foo"));
+    }
+
+    #[test]
+    fn normalized_identifiers_agree_with_symbols() {
+        let symtab = SymbolTable::default();
+        let sym0 = symtab.insert_utf8("Hello");
+        let sym1 = symtab.insert_utf8("hELLO");
+        assert_eq!(sym0, sym1);
+        assert!(identifiers_equal(&sym0.name_utf8(), &sym1.name_utf8()));
+    }
 }
@@ -13,11 +13,21 @@ mod data;
 mod project;
 mod syntax;
 
+pub use crate::analysis::{
+    Metrics, ResolutionStrictness, SourceMetrics, UnitMetrics, UnitTiming, VHDLStandard,
+};
 pub use crate::config::Config;
+#[cfg(feature = "json")]
+pub use crate::data::diagnostics_to_json;
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType, Position,
-    Range, Severity, Source, SrcPos,
+    codes_present, dedup, find_homographs, group_by_position, identifiers_equal,
+    messages_with_code, normalize_identifier, sort_by_position, summary, Diagnostic,
+    DiagnosticCallback, DiagnosticHandler, DowngradedDiagnostics, GroupedDiagnostic, Latin1String,
+    Message, MessageCallback, MessageHandler, MessagePrinter, MessageType, NormalizedIdent, Origin,
+    Position, Range, Severity, Source, SrcPos, TextEdit,
 };
 
-pub use crate::project::{Project, SourceFile};
+pub use crate::project::{
+    analyze_design_file, analyze_design_file_with_config, Project, SourceFile,
+};
 pub use crate::syntax::{ParserResult, VHDLParser};
@@ -14,7 +14,7 @@ extern crate clap;
 
 use std::path::Path;
 use std::time::SystemTime;
-use vhdl_lang::{Config, Diagnostic, MessagePrinter, Project};
+use vhdl_lang::{summary, Config, Diagnostic, MessagePrinter, Project};
 
 fn main() {
     use clap::{App, Arg};
@@ -35,6 +35,12 @@ fn main() {
                 .help("Prints the number of files processed and the execution time")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("summary")
+                .long("--summary")
+                .help("Prints the number of errors, warnings and hints after the diagnostics")
+                .takes_value(false),
+        )
         .arg(
             Arg::with_name("config")
                 .help("Config file in TOML format containing libraries and settings")
@@ -54,6 +60,7 @@ fn main() {
     }
 
     let show_perf = matches.is_present("perf");
+    let show_summary = matches.is_present("summary");
 
     let file_name = value_t_or_exit!(matches.value_of("config"), String);
     let mut config = Config::default();
@@ -70,6 +77,10 @@ fn main() {
     let duration = start.elapsed().unwrap();
     show_diagnostics(&diagnostics);
 
+    if show_summary {
+        println!("{}", summary(&diagnostics));
+    }
+
     if show_perf {
         let mut num_files = 0;
         let mut num_lines = 0;
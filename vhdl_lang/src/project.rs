@@ -4,20 +4,32 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::DesignRoot;
-use crate::ast::DesignFile;
+use crate::analysis::{
+    timed, DesignRoot, Metrics, ResolutionStrictness, SourceMetrics, VHDLStandard,
+};
+use crate::ast::search::NameReference;
+use crate::ast::{DesignFile, Designator, UseClause};
 use crate::config::Config;
 use crate::data::*;
 use crate::syntax::VHDLParser;
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub struct Project {
     parser: VHDLParser,
     root: DesignRoot,
     files: FnvHashMap<PathBuf, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
+    metrics: bool,
+    lints: bool,
+    strict: bool,
+    resolution_strictness: ResolutionStrictness,
+    standard: VHDLStandard,
+    downgraded_codes: Vec<String>,
+    conditional_identifiers: Option<Vec<(String, String)>>,
 }
 
 impl Project {
@@ -28,6 +40,13 @@ impl Project {
             files: FnvHashMap::default(),
             empty_libraries: FnvHashSet::default(),
             parser,
+            metrics: false,
+            lints: false,
+            strict: false,
+            resolution_strictness: ResolutionStrictness::Strict,
+            standard: VHDLStandard::VHDL2008,
+            downgraded_codes: Vec::new(),
+            conditional_identifiers: None,
         }
     }
 
@@ -47,7 +66,18 @@ impl Project {
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: &Config, messages: &mut dyn MessageHandler) {
         self.parser = VHDLParser::default();
+        if let Some(ref identifiers) = self.conditional_identifiers {
+            self.parser.enable_conditional_analysis(identifiers.clone());
+        }
         self.root = DesignRoot::new(self.parser.symbols.clone());
+        self.root.enable_metrics(self.metrics);
+        self.root.enable_lints(self.lints);
+        if self.strict {
+            self.root.enable_strict_mode();
+        }
+        self.root
+            .set_resolution_strictness(self.resolution_strictness);
+        self.root.set_standard(self.standard);
 
         // Reset library associations for known files,
         // all project files are added to the corresponding libraries later on.
@@ -62,13 +92,19 @@ impl Project {
             .into_iter()
             .partition(|(file_name, _library_names)| self.files.contains_key(file_name));
 
+        let parser = &self.parser;
         for (file_name, library_names) in known_files {
             if let Some(source_file) = self.files.get_mut(&file_name) {
                 source_file.parser_diagnostics.clear();
                 source_file.library_names = library_names;
-                source_file.design_file = self
-                    .parser
-                    .parse_design_source(&source_file.source, &mut source_file.parser_diagnostics);
+                let (design_file, parse_time) = timed(self.metrics, || {
+                    parser.parse_design_source(
+                        &source_file.source,
+                        &mut source_file.parser_diagnostics,
+                    )
+                });
+                source_file.design_file = design_file;
+                source_file.parse_time = parse_time;
             }
         }
 
@@ -118,19 +154,23 @@ impl Project {
     ) {
         use rayon::prelude::*;
 
+        let metrics = self.metrics;
         let parsed: Vec<_> = files_to_parse
             .into_par_iter()
             .map_init(
                 || &self.parser,
                 |parser, (file_name, library_names)| {
                     let mut diagnostics = Vec::new();
-                    let result = parser.parse_design_file(&file_name, &mut diagnostics);
-                    (file_name, library_names, diagnostics, result)
+                    let (result, parse_time) = timed(metrics, || {
+                        parser.parse_design_file(&file_name, &mut diagnostics)
+                    });
+                    (file_name, library_names, diagnostics, result, parse_time)
                 },
             )
             .collect();
 
-        for (file_name, library_names, parser_diagnostics, result) in parsed.into_iter() {
+        for (file_name, library_names, parser_diagnostics, result, parse_time) in parsed.into_iter()
+        {
             let (source, design_file) = match result {
                 Ok(result) => result,
                 Err(err) => {
@@ -146,6 +186,7 @@ impl Project {
                     library_names,
                     parser_diagnostics,
                     design_file,
+                    parse_time,
                 },
             );
         }
@@ -172,19 +213,25 @@ impl Project {
                     library_names: FnvHashSet::default(),
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
+                    parse_time: None,
                 }
             }
         };
         source_file.parser_diagnostics.clear();
-        source_file.design_file = self
-            .parser
-            .parse_design_source(source, &mut source_file.parser_diagnostics);
+        let (design_file, parse_time) = timed(self.metrics, || {
+            self.parser
+                .parse_design_source(source, &mut source_file.parser_diagnostics)
+        });
+        source_file.design_file = design_file;
+        source_file.parse_time = parse_time;
         self.files
             .insert(source.file_name().to_owned(), source_file);
     }
 
     pub fn analyse(&mut self) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
+        let mut all_diagnostics = Vec::new();
+        let mut diagnostics =
+            DowngradedDiagnostics::new(self.downgraded_codes.iter(), &mut all_diagnostics);
 
         for source_file in self.files.values_mut() {
             let design_file = source_file.take_design_file();
@@ -206,7 +253,70 @@ impl Project {
         }
 
         self.root.analyze(&mut diagnostics);
-        diagnostics
+        all_diagnostics
+    }
+
+    /// Report errors with any of the given codes as warnings from now on,
+    /// such as to tolerate a rule temporarily while migrating a code base
+    pub fn set_downgraded_codes(&mut self, codes: impl IntoIterator<Item = impl Into<String>>) {
+        self.downgraded_codes = codes.into_iter().map(|code| code.into()).collect();
+    }
+
+    /// Enable or disable opt-in checks that are not required by the LRM
+    pub fn enable_lints(&mut self, enable: bool) {
+        self.lints = enable;
+        self.root.enable_lints(enable);
+    }
+
+    /// Only report the errors required by the LRM, opt-in lints stay disabled
+    /// even if they are enabled afterwards
+    pub fn enable_strict_mode(&mut self) {
+        self.strict = true;
+        self.root.enable_strict_mode();
+    }
+
+    /// Evaluate conditional analysis tool directives against the given identifier values
+    /// in sources parsed from now on, the identifiers are kept when the configuration is updated
+    pub fn enable_conditional_analysis(
+        &mut self,
+        identifiers: impl IntoIterator<Item = (String, String)>,
+    ) {
+        let identifiers: Vec<_> = identifiers.into_iter().collect();
+        self.parser.enable_conditional_analysis(identifiers.clone());
+        self.conditional_identifiers = Some(identifiers);
+    }
+
+    /// Set how names selected from a library with a missing primary unit are reported
+    pub fn set_resolution_strictness(&mut self, strictness: ResolutionStrictness) {
+        self.resolution_strictness = strictness;
+        self.root.set_resolution_strictness(strictness);
+    }
+
+    /// Set the revision of the VHDL standard, VHDL-2008 by default
+    pub fn set_standard(&mut self, standard: VHDLStandard) {
+        self.standard = standard;
+        self.root.set_standard(standard);
+    }
+
+    /// Enable or disable timing of parsing and of the analysis phases of each design unit
+    /// Only sources parsed while enabled have a parse time
+    pub fn enable_metrics(&mut self, enable: bool) {
+        self.metrics = enable;
+        self.root.enable_metrics(enable);
+    }
+
+    /// The time spent parsing each source and analyzing each design unit
+    pub fn metrics(&self) -> Metrics {
+        let mut metrics = self.root.metrics();
+        for source_file in self.files.values() {
+            if let Some(parse) = source_file.parse_time {
+                metrics.sources.push(SourceMetrics {
+                    source: source_file.source.clone(),
+                    parse,
+                });
+            }
+        }
+        metrics
     }
 
     /// Search for reference at position
@@ -224,9 +334,172 @@ impl Project {
         self.root.find_all_references(decl_pos)
     }
 
+    /// Expand a 'use lib.pkg.all' clause into the names it makes potentially visible
+    pub fn expand_use_all(
+        &self,
+        library_name: &Symbol,
+        use_clause: &UseClause,
+    ) -> Result<Vec<Designator>, Diagnostic> {
+        self.root.expand_use_all(library_name, use_clause)
+    }
+
+    /// Check if a name is visible within the design unit at the cursor
+    pub fn is_visible(&self, source: &Source, cursor: Position, name: &str) -> bool {
+        self.root.is_visible(source, cursor, name)
+    }
+
+    /// Selected names within the design unit at the cursor which do not resolve locally
+    pub fn external_references(&self, source: &Source, cursor: Position) -> Vec<(Symbol, SrcPos)> {
+        self.root.external_references(source, cursor)
+    }
+
+    /// All names within the design unit at the cursor and the declarations they resolve to
+    pub fn references(&self, source: &Source, cursor: Position) -> Vec<NameReference> {
+        self.root.references(source, cursor)
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &SourceFile> {
         self.files.values()
     }
+
+    /// Update the source and return only the diagnostics introduced by the new contents
+    /// Diagnostics of the previous contents are matched through a line based diff
+    /// such that a diagnostic which only moved to another line is not new
+    ///
+    /// The previous contents are taken from the source of the project, so the new contents
+    /// must be a new source rather than the source of the project changed in place
+    pub fn new_diagnostics(&mut self, source: &Source) -> Vec<Diagnostic> {
+        // Take a copy of the previous lines before the source of the project is replaced
+        let old_lines = self
+            .get_source(source.file_name())
+            .map(|old_source| source_lines(&old_source));
+        let old_diagnostics = self.analyse();
+        self.update_source(source);
+        let new_diagnostics = self.analyse();
+
+        let line_map = old_lines.map(|old_lines| map_lines(&old_lines, &source_lines(source)));
+
+        let mut old_keys: FnvHashMap<DiagnosticKey, usize> = FnvHashMap::default();
+        for diagnostic in old_diagnostics.iter() {
+            let line_offset = if diagnostic.pos.file_name() == source.file_name() {
+                let line = diagnostic.pos.start().line as usize;
+                match line_map
+                    .as_ref()
+                    .and_then(|line_map| line_map.get(line).cloned().flatten())
+                {
+                    Some(new_line) => new_line as i64 - line as i64,
+                    // The line was changed or removed
+                    None => continue,
+                }
+            } else {
+                0
+            };
+            *old_keys
+                .entry(diagnostic_key(diagnostic, line_offset))
+                .or_insert(0) += 1;
+        }
+
+        new_diagnostics
+            .into_iter()
+            .filter(
+                |diagnostic| match old_keys.get_mut(&diagnostic_key(diagnostic, 0)) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                },
+            )
+            .collect()
+    }
+}
+
+/// Identifies a diagnostic by everything except the source contents
+type DiagnosticKey = (PathBuf, i64, Range, String, Severity, Option<&'static str>);
+
+fn diagnostic_key(diagnostic: &Diagnostic, line_offset: i64) -> DiagnosticKey {
+    let range = diagnostic.pos.range();
+    let start_line = range.start.line as i64 + line_offset;
+    (
+        diagnostic.pos.file_name().to_owned(),
+        start_line,
+        // The line of the range is relative to its start
+        Range::new(
+            Position::new(0, range.start.character),
+            Position::new(range.end.line - range.start.line, range.end.character),
+        ),
+        diagnostic.message.clone(),
+        diagnostic.severity,
+        diagnostic.code,
+    )
+}
+
+/// Larger changes than this number of lines squared only match unchanged leading and trailing lines
+const MAX_DIFF_SIZE: usize = 1 << 22;
+
+/// Map each old line to its index within the new lines, None when it was changed
+fn map_lines(old_lines: &[String], new_lines: &[String]) -> Vec<Option<u32>> {
+    let mut line_map = vec![None; old_lines.len()];
+
+    let prefix = old_lines
+        .iter()
+        .zip(new_lines.iter())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(old_line, new_line)| old_line == new_line)
+        .count();
+
+    for (line, mapped) in line_map.iter_mut().take(prefix).enumerate() {
+        *mapped = Some(line as u32);
+    }
+    for line in 0..suffix {
+        line_map[old_lines.len() - 1 - line] = Some((new_lines.len() - 1 - line) as u32);
+    }
+
+    // Longest common subsequence of the changed lines in between
+    let old_changed = &old_lines[prefix..old_lines.len() - suffix];
+    let new_changed = &new_lines[prefix..new_lines.len() - suffix];
+    if old_changed.len() * new_changed.len() > MAX_DIFF_SIZE {
+        return line_map;
+    }
+
+    let width = new_changed.len() + 1;
+    let mut lengths = vec![0_u32; (old_changed.len() + 1) * width];
+    for i in (0..old_changed.len()).rev() {
+        for j in (0..new_changed.len()).rev() {
+            lengths[i * width + j] = if old_changed[i] == new_changed[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_changed.len() && j < new_changed.len() {
+        if old_changed[i] == new_changed[j] {
+            line_map[prefix + i] = Some((prefix + j) as u32);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    line_map
+}
+
+fn source_lines(source: &Source) -> Vec<String> {
+    let contents = source.contents();
+    (0..contents.num_lines())
+        .filter_map(|lineno| contents.get_line(lineno).map(|line| line.to_owned()))
+        .collect()
 }
 
 /// Multiply clonable value by cloning
@@ -246,6 +519,35 @@ fn multiply<T: Clone>(value: T, n: usize) -> Vec<T> {
     }
 }
 
+/// Parse and analyze a single file as the library work together with the installed
+/// libraries such as std and ieee
+///
+/// Syntax errors are returned together with the analysis diagnostics rather than
+/// aborting. Messages from loading the external configuration are discarded.
+pub fn analyze_design_file(file_name: &Path) -> io::Result<Vec<Diagnostic>> {
+    let mut config = Config::default();
+    let mut messages = Vec::new();
+    config.load_external_config(&mut messages);
+    analyze_design_file_with_config(file_name, &config, &mut messages)
+}
+
+/// Parse and analyze a single file as the library work together with the libraries of the
+/// given configuration
+pub fn analyze_design_file_with_config(
+    file_name: &Path,
+    config: &Config,
+    messages: &mut dyn MessageHandler,
+) -> io::Result<Vec<Diagnostic>> {
+    let source = Source::from_latin1_file(file_name)?;
+    let mut project = Project::from_config(config, messages);
+    project.update_source(&source);
+    let work = project.parser.symbol(&Latin1String::new(b"work"));
+    if let Some(source_file) = project.files.get_mut(source.file_name()) {
+        source_file.library_names.insert(work);
+    }
+    Ok(project.analyse())
+}
+
 impl Default for Project {
     fn default() -> Self {
         Self::new()
@@ -257,6 +559,7 @@ pub struct SourceFile {
     source: Source,
     design_file: DesignFile,
     parser_diagnostics: Vec<Diagnostic>,
+    parse_time: Option<Duration>,
 }
 
 impl SourceFile {
@@ -520,4 +823,229 @@ lib.files = ['file2.vhd']
         assert_eq!(diagnostics[0].pos.source, source2); // No such library
         assert_eq!(diagnostics[1].pos.source, source2); // No declaration
     }
+
+    #[test]
+    fn conditional_analysis_is_kept_on_config_update() {
+        let source = Source::inline(
+            Path::new("file.vhd"),
+            "
+`if VHDL_VERSION = \"2008\" then
+package pkg is
+end package;
+`else
+package pkg is
+end package;
+package pkg is
+end package;
+`end if
+",
+        );
+
+        let mut messages = Vec::new();
+        let mut project = Project::new();
+        project.enable_conditional_analysis(vec![("VHDL_VERSION".to_owned(), "2008".to_owned())]);
+        project.update_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        project.update_source(&source);
+        let file = &project.files[source.file_name()];
+        check_no_diagnostics(&file.parser_diagnostics);
+        assert_eq!(file.design_file.design_units.len(), 1);
+    }
+
+    #[test]
+    fn test_metrics_contain_parse_time_when_enabled() {
+        let source = Source::inline(
+            Path::new("file.vhd"),
+            "
+package pkg is
+end package;
+        ",
+        );
+
+        let mut project = Project::new();
+        project.update_source(&source);
+        assert!(project.metrics().sources.is_empty());
+
+        project.enable_metrics(true);
+        project.update_source(&source);
+        let metrics = project.metrics();
+        assert_eq!(metrics.sources.len(), 1);
+        assert_eq!(metrics.sources[0].source, source);
+    }
+
+    /// A package with a duplicate declaration of 'a' on line 4
+    const DUPLICATE_PACKAGE: &str = "
+package pkg is
+  type t is (v);
+  constant a : t := v;
+  constant a : t := v;
+end package;
+";
+
+    /// Parse the source into library 'lib' of the project
+    fn add_to_library(project: &mut Project, source: &Source) {
+        project.update_source(source);
+        let library_name = project.parser.symbol(&Latin1String::new(b"lib"));
+        project
+            .files
+            .get_mut(source.file_name())
+            .unwrap()
+            .library_names
+            .insert(library_name);
+    }
+
+    /// An architecture with a component that is never instantiated
+    const UNUSED_COMPONENT: &str = "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp1 is
+  end component;
+begin
+end architecture;
+";
+
+    #[test]
+    fn lints_are_kept_on_config_update() {
+        let source = Source::inline(Path::new("file.vhd"), UNUSED_COMPONENT);
+
+        let mut messages = Vec::new();
+        let mut project = Project::new();
+        project.enable_lints(true);
+        project.update_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        add_to_library(&mut project, &source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Component 'comp1' is declared but never instantiated"
+        );
+    }
+
+    #[test]
+    fn strict_mode_is_kept_on_config_update() {
+        let source = Source::inline(Path::new("file.vhd"), UNUSED_COMPONENT);
+
+        let mut messages = Vec::new();
+        let mut project = Project::new();
+        project.enable_strict_mode();
+        project.enable_lints(true);
+        project.update_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        add_to_library(&mut project, &source);
+        check_no_diagnostics(&project.analyse());
+    }
+
+    #[test]
+    fn resolution_strictness_is_kept_on_config_update() {
+        let source = Source::inline(
+            Path::new("file.vhd"),
+            "
+package pkg is
+  type t is (v);
+  constant c0 : t := work.other.thing;
+end package;
+",
+        );
+
+        let mut messages = Vec::new();
+        let mut project = Project::new();
+        project.set_resolution_strictness(ResolutionStrictness::Lenient);
+        project.update_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        add_to_library(&mut project, &source);
+        check_no_diagnostics(&project.analyse());
+    }
+
+    #[test]
+    fn standard_is_kept_on_config_update() {
+        let source = Source::inline(
+            Path::new("file.vhd"),
+            "
+package pkg is
+  type t is (v);
+  type arr_t is array (t range <>) of t;
+  type rec_t is record
+    arr : arr_t;
+  end record;
+end package;
+",
+        );
+
+        let mut messages = Vec::new();
+        let mut project = Project::new();
+        project.set_standard(VHDLStandard::VHDL1993);
+        project.update_config(&Config::default(), &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let standard = Source::inline(
+            Path::new("standard.vhd"),
+            &Latin1String::new(include_bytes!("../../vhdl_libraries/std/standard.vhd")).to_string(),
+        );
+        project.update_source(&standard);
+        let std_sym = project.parser.symbol(&Latin1String::new(b"std"));
+        project
+            .files
+            .get_mut(standard.file_name())
+            .unwrap()
+            .library_names
+            .insert(std_sym);
+        add_to_library(&mut project, &source);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Record element 'arr' must have a constrained subtype"
+        );
+    }
+
+    #[test]
+    fn new_diagnostics_only_reports_introduced_homograph() {
+        let path = Path::new("file.vhd");
+        let mut project = Project::new();
+        add_to_library(&mut project, &Source::inline(path, DUPLICATE_PACKAGE));
+
+        // The existing homograph of 'a' moves two lines down
+        let source = Source::inline(
+            path,
+            "
+package pkg is
+  type t is (v);
+  constant b : t := v;
+  constant b : t := v;
+  constant a : t := v;
+  constant a : t := v;
+end package;
+",
+        );
+
+        let diagnostics = project.new_diagnostics(&source);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Duplicate declaration of 'b'");
+        assert_eq!(diagnostics[0].pos.start(), Position::new(4, 11));
+    }
+
+    #[test]
+    fn downgraded_codes_are_reported_as_warnings() {
+        let mut project = Project::new();
+        add_to_library(
+            &mut project,
+            &Source::inline(Path::new("file.vhd"), DUPLICATE_PACKAGE),
+        );
+
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        project.set_downgraded_codes(vec!["duplicate-declaration"]);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
 }
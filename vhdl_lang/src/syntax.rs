@@ -12,11 +12,13 @@ mod attributes;
 mod common;
 mod component_declaration;
 mod concurrent_statement;
+mod conditional_analysis;
 mod configuration;
 mod context;
 mod declarative_part;
 mod design_unit;
 mod expression;
+mod group_declaration;
 mod interface_declaration;
 mod names;
 mod object_declaration;
@@ -13,8 +13,9 @@ use crate::ast::{
     Attribute, AttributeDeclaration, AttributeSpecification, Designator, EntityClass, EntityName,
     EntityTag,
 };
+use crate::data::{Diagnostic, DiagnosticHandler, Origin};
 
-fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
+pub fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
     let token = stream.expect()?;
     Ok(try_token_kind!(
         token,
@@ -29,11 +30,18 @@ fn parse_entity_class(stream: &mut TokenStream) -> ParseResult<EntityClass> {
         Component => EntityClass::Component,
         Constant => EntityClass::Constant,
         Type => EntityClass::Type,
-        Label => EntityClass::Label
+        Subtype => EntityClass::Subtype,
+        Label => EntityClass::Label,
+        Units => EntityClass::Units,
+        Group => EntityClass::Group,
+        File => EntityClass::File
     ))
 }
 
-pub fn parse_entity_name_list(stream: &mut TokenStream) -> ParseResult<Vec<EntityName>> {
+pub fn parse_entity_name_list(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Vec<EntityName>> {
     let token = stream.peek_expect()?;
     Ok(try_token_kind!(
         token,
@@ -68,6 +76,7 @@ pub fn parse_entity_name_list(stream: &mut TokenStream) -> ParseResult<Vec<Entit
                         stream.move_after(&sep_token);
                     },
                     Colon => {
+                        check_unique_entity_names(&entity_name_list, diagnostics);
                         break entity_name_list;
                     }
                 )
@@ -84,7 +93,50 @@ pub fn parse_entity_name_list(stream: &mut TokenStream) -> ParseResult<Vec<Entit
     ))
 }
 
-pub fn parse_attribute(stream: &mut TokenStream) -> ParseResult<Vec<Attribute>> {
+/// An entity name without a signature may only occur once in the entity name list
+fn check_unique_entity_names(
+    entity_name_list: &[EntityName],
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    for (idx, entity_name) in entity_name_list.iter().enumerate() {
+        if let EntityName::Name(EntityTag {
+            designator,
+            signature: None,
+        }) = entity_name
+        {
+            let previous = entity_name_list[..idx].iter().find(|prev| {
+                if let EntityName::Name(EntityTag {
+                    designator: prev_designator,
+                    signature: None,
+                }) = prev
+                {
+                    prev_designator.item == designator.item
+                } else {
+                    false
+                }
+            });
+
+            if let Some(EntityName::Name(previous)) = previous {
+                diagnostics.push(
+                    Diagnostic::error(
+                        designator,
+                        format!(
+                            "Duplicate entity '{}' in attribute specification",
+                            designator.item
+                        ),
+                    )
+                    .related(&previous.designator, "Previously specified here")
+                    .with_origin(Origin::Semantic),
+                );
+            }
+        }
+    }
+}
+
+pub fn parse_attribute(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Vec<Attribute>> {
     stream.expect_kind(Attribute)?;
     let ident = stream.expect_ident()?;
     let token = stream.expect()?;
@@ -100,7 +152,7 @@ pub fn parse_attribute(stream: &mut TokenStream) -> ParseResult<Vec<Attribute>>
             })]
         },
         Of => {
-            let entity_names = parse_entity_name_list(stream)?;
+            let entity_names = parse_entity_name_list(stream, diagnostics)?;
             stream.expect_kind(Colon)?;
             let entity_class = parse_entity_class(stream)?;
             stream.expect_kind(Is)?;
@@ -130,7 +182,7 @@ mod tests {
     fn parse_simple_attribute_declaration() {
         let code = Code::new("attribute foo : lib.name;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Declaration(AttributeDeclaration {
                 ident: code.s1("foo").ident(),
                 type_mark: code.s1("lib.name").selected_name()
@@ -142,7 +194,7 @@ mod tests {
     fn parse_simple_attribute_specification() {
         let code = Code::new("attribute attr_name of foo : signal is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Specification(AttributeSpecification {
                 ident: code.s1("attr_name").ident(),
                 entity_name: EntityName::Name(EntityTag {
@@ -159,7 +211,7 @@ mod tests {
     fn parse_simple_attribute_specification_operator_symbol() {
         let code = Code::new("attribute attr_name of \"**\" : function is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Specification(AttributeSpecification {
                 ident: code.s1("attr_name").ident(),
                 entity_name: EntityName::Name(EntityTag {
@@ -176,7 +228,7 @@ mod tests {
     fn parse_attribute_specification_list() {
         let code = Code::new("attribute attr_name of foo, bar : signal is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![
                 Attribute::Specification(AttributeSpecification {
                     ident: code.s1("attr_name").ident(),
@@ -204,7 +256,7 @@ mod tests {
     fn parse_attribute_specification_all() {
         let code = Code::new("attribute attr_name of all : signal is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Specification(AttributeSpecification {
                 ident: code.s1("attr_name").ident(),
                 entity_name: EntityName::All,
@@ -218,7 +270,7 @@ mod tests {
     fn parse_attribute_specification_others() {
         let code = Code::new("attribute attr_name of others : signal is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Specification(AttributeSpecification {
                 ident: code.s1("attr_name").ident(),
                 entity_name: EntityName::Others,
@@ -232,7 +284,7 @@ mod tests {
     fn parse_attribute_specification_with_signature() {
         let code = Code::new("attribute attr_name of foo[return natural] : function is 0+1;");
         assert_eq!(
-            code.with_stream(parse_attribute),
+            code.with_stream_no_diagnostics(parse_attribute),
             vec![Attribute::Specification(AttributeSpecification {
                 ident: code.s1("attr_name").ident(),
                 entity_name: EntityName::Name(EntityTag {
@@ -244,4 +296,20 @@ mod tests {
             })]
         )
     }
+
+    #[test]
+    fn parse_attribute_specification_list_with_duplicate() {
+        let code = Code::new("attribute attr_name of foo, bar, foo : signal is 0+1;");
+        let (specs, diagnostics) = code.with_stream_diagnostics(parse_attribute);
+        assert_eq!(specs.len(), 3);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s("foo", 2),
+                "Duplicate entity 'foo' in attribute specification"
+            )
+            .related(code.s1("foo"), "Previously specified here")
+            .with_origin(Origin::Semantic)]
+        );
+    }
 }
@@ -364,6 +364,18 @@ fn parse_generate_body_end_token(
     alternative_label: Option<Ident>,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<(GenerateBody, Token)> {
+    // Only a block statement may have a generic or port clause
+    let token = stream.peek_expect()?;
+    let is_interface_clause = match token.kind {
+        Generic | Port => true,
+        _ => false,
+    };
+    if is_interface_clause {
+        return Err(Diagnostic::error(
+            token,
+            "Port/generic clause not allowed in generate statement",
+        ));
+    }
     let decl = parse_optional_declarative_part(stream, diagnostics)?;
     let (statements, mut end_token) =
         parse_labeled_concurrent_statements_end_token(stream, diagnostics)?;
@@ -1452,6 +1464,29 @@ end generate;
         assert_eq!(stmt.statement, ConcurrentStatement::ForGenerate(gen));
     }
 
+    #[test]
+    fn test_for_generate_rejects_port_clause() {
+        let code = Code::new(
+            "\
+gen: for idx in 0 to 1 generate
+  port (foo : in bit);
+begin
+end generate;
+",
+        );
+        let (result, diagnostics) = code.with_partial_stream_diagnostics(|stream, diagnostics| {
+            parse_labeled_concurrent_statement(stream, diagnostics)
+        });
+        assert_eq!(diagnostics, vec![]);
+        assert_eq!(
+            result,
+            Err(Diagnostic::error(
+                code.s1("port"),
+                "Port/generic clause not allowed in generate statement"
+            ))
+        );
+    }
+
     #[test]
     fn test_if_generate_empty() {
         let code = Code::new(
@@ -0,0 +1,629 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+//! LRM 24.2 Conditional analysis tool directives
+//!
+//! The directives are evaluated before tokenization. Directive lines and excluded
+//! regions are replaced by whitespace so that the source positions of included text
+//! are unchanged.
+
+use crate::data::*;
+use fnv::FnvHashMap;
+
+/// Maps conditional analysis identifiers such as VHDL_VERSION to their values
+pub(crate) type ConditionalIdentifiers = FnvHashMap<NormalizedIdent, String>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Identifier(String),
+    StringLiteral(String),
+    LeftPar,
+    RightPar,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Illegal(char),
+}
+
+#[derive(Clone, Debug)]
+struct DirectiveToken {
+    kind: Kind,
+    pos: SrcPos,
+}
+
+impl DirectiveToken {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self.kind {
+            Kind::Identifier(ref name) => name.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+/// Tokenize the text of a directive line from the character after the backtick
+/// up to the end of the line or a trailing comment
+fn tokenize_directive(
+    source: &Source,
+    lineno: u32,
+    start: u32,
+    text: &str,
+) -> Result<Vec<DirectiveToken>, Diagnostic> {
+    let chars: Vec<(u32, char)> = text
+        .chars()
+        .scan(start, |character, chr| {
+            let current = *character;
+            *character += chr.len_utf16() as u32;
+            Some((current, chr))
+        })
+        .collect();
+
+    let pos_of = |from: usize, to: usize| {
+        let end = chars
+            .get(to)
+            .map(|(character, _)| *character)
+            .unwrap_or_else(|| {
+                let (character, chr) = chars[to - 1];
+                character + chr.len_utf16() as u32
+            });
+        source.pos(
+            Position::new(lineno, chars[from].0),
+            Position::new(lineno, end),
+        )
+    };
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let chr = chars[i].1;
+        let start = i;
+
+        let kind = if chr.is_whitespace() {
+            i += 1;
+            continue;
+        } else if chr == '-' && chars.get(i + 1).map(|(_, chr)| *chr) == Some('-') {
+            break;
+        } else if chr.is_alphanumeric() || chr == '_' {
+            while i < chars.len() && (chars[i].1.is_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            Kind::Identifier(chars[start..i].iter().map(|(_, chr)| *chr).collect())
+        } else if chr == '"' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i).map(|(_, chr)| *chr) {
+                    Some('"') if chars.get(i + 1).map(|(_, chr)| *chr) == Some('"') => {
+                        value.push('"');
+                        i += 2;
+                    }
+                    Some('"') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(chr) => {
+                        value.push(chr);
+                        i += 1;
+                    }
+                    None => {
+                        return Err(Diagnostic::error(
+                            pos_of(start, i),
+                            "Reached end of line when parsing string literal",
+                        ));
+                    }
+                }
+            }
+            Kind::StringLiteral(value)
+        } else {
+            let next = chars.get(i + 1).map(|(_, chr)| *chr);
+            let (kind, len) = match (chr, next) {
+                ('(', _) => (Kind::LeftPar, 1),
+                (')', _) => (Kind::RightPar, 1),
+                ('=', _) => (Kind::Equal, 1),
+                ('/', Some('=')) => (Kind::NotEqual, 2),
+                ('<', Some('=')) => (Kind::LessEqual, 2),
+                ('<', _) => (Kind::Less, 1),
+                ('>', Some('=')) => (Kind::GreaterEqual, 2),
+                ('>', _) => (Kind::Greater, 1),
+                (chr, _) => (Kind::Illegal(chr), 1),
+            };
+            i += len;
+            kind
+        };
+
+        tokens.push(DirectiveToken {
+            kind,
+            pos: pos_of(start, i),
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent evaluation of a directive condition, LRM 24.2.3
+struct ConditionParser<'a> {
+    tokens: &'a [DirectiveToken],
+    idx: usize,
+    identifiers: &'a ConditionalIdentifiers,
+    directive_pos: &'a SrcPos,
+}
+
+impl<'a> ConditionParser<'a> {
+    fn peek(&self) -> Option<&'a DirectiveToken> {
+        self.tokens.get(self.idx)
+    }
+
+    fn expect(&mut self, what: &str) -> Result<&'a DirectiveToken, Diagnostic> {
+        if let Some(token) = self.peek() {
+            self.idx += 1;
+            Ok(token)
+        } else {
+            let pos = self
+                .tokens
+                .last()
+                .map(|token| &token.pos)
+                .unwrap_or(self.directive_pos);
+            Err(Diagnostic::error(pos, format!("Expected {}", what)))
+        }
+    }
+
+    fn condition(&mut self) -> Result<bool, Diagnostic> {
+        let mut value = self.relation()?;
+        let mut operator: Option<&str> = None;
+
+        while let Some(token) = self.peek() {
+            let (name, apply): (&str, fn(bool, bool) -> bool) = if token.is_keyword("and") {
+                ("and", |l, r| l && r)
+            } else if token.is_keyword("or") {
+                ("or", |l, r| l || r)
+            } else if token.is_keyword("xor") {
+                ("xor", |l, r| l != r)
+            } else if token.is_keyword("xnor") {
+                ("xnor", |l, r| l == r)
+            } else {
+                break;
+            };
+
+            if let Some(first) = operator {
+                if first != name {
+                    return Err(Diagnostic::error(
+                        &token.pos,
+                        "Mixed logical operators require parentheses",
+                    ));
+                }
+            }
+            operator = Some(name);
+            self.idx += 1;
+
+            let rhs = self.relation()?;
+            value = apply(value, rhs);
+        }
+
+        Ok(value)
+    }
+
+    fn parenthesized(&mut self) -> Result<bool, Diagnostic> {
+        let value = self.condition()?;
+        let token = self.expect("')'")?;
+        if token.kind != Kind::RightPar {
+            return Err(Diagnostic::error(&token.pos, "Expected ')'"));
+        }
+        Ok(value)
+    }
+
+    fn relation(&mut self) -> Result<bool, Diagnostic> {
+        let token = self.expect("conditional expression")?;
+
+        match token.kind {
+            Kind::LeftPar => self.parenthesized(),
+            Kind::Identifier(_) if token.is_keyword("not") => {
+                let left_par = self.expect("'('")?;
+                if left_par.kind != Kind::LeftPar {
+                    return Err(Diagnostic::error(&left_par.pos, "Expected '('"));
+                }
+                Ok(!self.parenthesized()?)
+            }
+            Kind::Identifier(ref name) => {
+                let value = self
+                    .identifiers
+                    .get(&normalize_identifier(name))
+                    .ok_or_else(|| {
+                        Diagnostic::error(
+                            &token.pos,
+                            format!("Undefined conditional analysis identifier '{}'", name),
+                        )
+                    })?;
+
+                let operator = self.expect("relational operator")?;
+                let literal = self.expect("string literal")?;
+                let literal = if let Kind::StringLiteral(ref literal) = literal.kind {
+                    literal.as_str()
+                } else {
+                    return Err(Diagnostic::error(&literal.pos, "Expected string literal"));
+                };
+
+                let value = value.as_str();
+                match operator.kind {
+                    Kind::Equal => Ok(value == literal),
+                    Kind::NotEqual => Ok(value != literal),
+                    Kind::Less => Ok(value < literal),
+                    Kind::LessEqual => Ok(value <= literal),
+                    Kind::Greater => Ok(value > literal),
+                    Kind::GreaterEqual => Ok(value >= literal),
+                    _ => Err(Diagnostic::error(
+                        &operator.pos,
+                        "Expected relational operator",
+                    )),
+                }
+            }
+            _ => Err(Diagnostic::error(
+                &token.pos,
+                "Expected conditional expression",
+            )),
+        }
+    }
+}
+
+/// Evaluate the condition of an `if or `elsif directive which must be followed by then
+fn evaluate_condition(
+    directive_pos: &SrcPos,
+    tokens: &[DirectiveToken],
+    identifiers: &ConditionalIdentifiers,
+) -> Result<bool, Diagnostic> {
+    let mut parser = ConditionParser {
+        tokens,
+        idx: 0,
+        identifiers,
+        directive_pos,
+    };
+
+    let value = parser.condition()?;
+    let then = parser.expect("'then'")?;
+    if !then.is_keyword("then") {
+        return Err(Diagnostic::error(&then.pos, "Expected 'then'"));
+    }
+    expect_end_of_directive(&tokens[parser.idx..])?;
+    Ok(value)
+}
+
+fn expect_end_of_directive(rest: &[DirectiveToken]) -> Result<(), Diagnostic> {
+    if let Some(token) = rest.first() {
+        Err(Diagnostic::error(
+            &token.pos,
+            "Expected end of tool directive",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// An `if directive which has not yet been closed by an `end
+struct Conditional {
+    pos: SrcPos,
+    /// The enclosing region is included
+    parent_included: bool,
+    /// The current branch is included
+    included: bool,
+    /// One of the branches has already been included
+    taken: bool,
+    seen_else: bool,
+}
+
+struct Preprocessor<'a> {
+    source: &'a Source,
+    identifiers: &'a ConditionalIdentifiers,
+    stack: Vec<Conditional>,
+}
+
+impl<'a> Preprocessor<'a> {
+    fn included(&self) -> bool {
+        self.stack.last().map(|cond| cond.included).unwrap_or(true)
+    }
+
+    fn directive(
+        &mut self,
+        directive_pos: SrcPos,
+        tokens: &[DirectiveToken],
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Result<(), Diagnostic> {
+        let keyword = match tokens.first() {
+            Some(DirectiveToken {
+                kind: Kind::Identifier(ref keyword),
+                ..
+            }) => keyword.to_ascii_lowercase(),
+            _ => {
+                return Err(Diagnostic::error(directive_pos, "Expected tool directive"));
+            }
+        };
+        let rest = &tokens[1..];
+
+        match keyword.as_str() {
+            "if" => {
+                let parent_included = self.included();
+                let condition = if parent_included {
+                    evaluate_condition(&directive_pos, rest, self.identifiers)
+                } else {
+                    Ok(false)
+                };
+
+                // The region is excluded when the condition is erroneous
+                // but the `if is still matched with its `end
+                let included = *condition.as_ref().unwrap_or(&false);
+                self.stack.push(Conditional {
+                    pos: directive_pos,
+                    parent_included,
+                    included,
+                    taken: included,
+                    seen_else: false,
+                });
+                condition?;
+            }
+            "elsif" | "else" => {
+                let identifiers = self.identifiers;
+                let cond = match self.stack.last_mut() {
+                    Some(cond) if !cond.seen_else => cond,
+                    Some(_) => {
+                        return Err(Diagnostic::error(
+                            directive_pos,
+                            format!("`{} directive after `else", keyword),
+                        ));
+                    }
+                    None => {
+                        return Err(Diagnostic::error(
+                            directive_pos,
+                            format!("`{} directive without matching `if", keyword),
+                        ));
+                    }
+                };
+
+                let branch = if !cond.parent_included || cond.taken {
+                    Ok(false)
+                } else if keyword == "elsif" {
+                    evaluate_condition(&directive_pos, rest, identifiers)
+                } else {
+                    expect_end_of_directive(rest).map(|_| true)
+                };
+
+                let included = *branch.as_ref().unwrap_or(&false);
+                cond.seen_else = keyword == "else";
+                cond.included = included;
+                cond.taken |= included;
+                branch?;
+            }
+            "end" => {
+                let rest = match rest.first() {
+                    Some(token) if token.is_keyword("if") => &rest[1..],
+                    _ => rest,
+                };
+                expect_end_of_directive(rest)?;
+
+                if self.stack.pop().is_none() {
+                    return Err(Diagnostic::error(
+                        directive_pos,
+                        "`end directive without matching `if",
+                    ));
+                }
+            }
+            "warning" | "error" => {
+                if !self.included() {
+                    return Ok(());
+                }
+
+                let message = match rest.first() {
+                    Some(DirectiveToken {
+                        kind: Kind::StringLiteral(ref message),
+                        ..
+                    }) => message,
+                    _ => {
+                        return Err(Diagnostic::error(
+                            directive_pos,
+                            format!("Expected string literal after `{}", keyword),
+                        ));
+                    }
+                };
+                expect_end_of_directive(&rest[1..])?;
+
+                if keyword == "warning" {
+                    diagnostics.warning(directive_pos, message.as_str());
+                } else {
+                    diagnostics.error(directive_pos, message.as_str());
+                }
+            }
+            _ => {
+                if self.included() {
+                    return Err(Diagnostic::error(
+                        directive_pos,
+                        format!("Unknown tool directive '`{}'", keyword),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Replace all characters except line endings by whitespace, keeping the UTF-16 length
+fn blank(line: &str, into: &mut String) {
+    for chr in line.chars() {
+        if chr == '\n' {
+            into.push(chr);
+        } else {
+            for _ in 0..chr.len_utf16() {
+                into.push(' ');
+            }
+        }
+    }
+}
+
+/// Evaluate the tool directives of the source returning the contents to tokenize
+pub(crate) fn evaluate_tool_directives(
+    source: &Source,
+    contents: &Contents,
+    identifiers: &ConditionalIdentifiers,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> Contents {
+    let mut preprocessor = Preprocessor {
+        source,
+        identifiers,
+        stack: Vec::new(),
+    };
+    let mut result = String::new();
+
+    for lineno in 0..contents.num_lines() {
+        let line = contents.get_line(lineno).unwrap();
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with('`') {
+            let directive = &trimmed['`'.len_utf8()..];
+            let lineno = lineno as u32;
+            let start: u32 = line[..line.len() - trimmed.len()]
+                .chars()
+                .map(|chr| chr.len_utf16() as u32)
+                .sum();
+            let directive_end = start + directive.trim_end().encode_utf16().count() as u32 + 1;
+            let directive_pos = preprocessor.source.pos(
+                Position::new(lineno, start),
+                Position::new(lineno, directive_end),
+            );
+
+            let evaluated = tokenize_directive(source, lineno, start + 1, directive)
+                .and_then(|tokens| preprocessor.directive(directive_pos, &tokens, diagnostics));
+            if let Err(diagnostic) = evaluated {
+                diagnostics.push(diagnostic);
+            }
+            blank(line, &mut result);
+        } else if preprocessor.included() {
+            result.push_str(line);
+        } else {
+            blank(line, &mut result);
+        }
+    }
+
+    for cond in preprocessor.stack {
+        diagnostics.error(cond.pos, "Missing `end for `if directive");
+    }
+
+    Contents::from_str(&result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::HasIdent;
+    use crate::syntax::test::{check_diagnostics, check_no_diagnostics, Code};
+    use crate::syntax::VHDLParser;
+
+    fn parser(version: &str) -> VHDLParser {
+        let mut identifiers = FnvHashMap::default();
+        identifiers.insert("VHDL_VERSION".to_owned(), version.to_owned());
+        identifiers.insert("TOOL_NAME".to_owned(), "rust_hdl".to_owned());
+
+        let mut parser = VHDLParser::default();
+        parser.enable_conditional_analysis(identifiers);
+        parser
+    }
+
+    fn unit_names(parser: &VHDLParser, code: &Code) -> (Vec<String>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let design_file = parser.parse_design_source(code.source(), &mut diagnostics);
+        let names = design_file
+            .design_units
+            .iter()
+            .map(|unit| unit.name().to_string())
+            .collect();
+        (names, diagnostics)
+    }
+
+    #[test]
+    fn includes_and_excludes_regions_by_condition() {
+        let code = Code::new(
+            "
+`if VHDL_VERSION >= \"2019\" and TOOL_NAME = \"rust_hdl\" then
+entity new_ent is
+end entity;
+`elsif VHDL_VERSION = \"2008\" then
+entity old_ent is
+end entity;
+`else
+entity ancient_ent is
+end entity;
+`end if
+",
+        );
+
+        let (names, diagnostics) = unit_names(&parser("2019"), &code);
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(names, vec!["new_ent".to_owned()]);
+
+        let (names, diagnostics) = unit_names(&parser("2008"), &code);
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(names, vec!["old_ent".to_owned()]);
+
+        let (names, diagnostics) = unit_names(&parser("1993"), &code);
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(names, vec!["ancient_ent".to_owned()]);
+    }
+
+    #[test]
+    fn preserves_positions_of_included_text() {
+        let code = Code::new(
+            "
+  `if not (VHDL_VERSION < \"2019\") then -- only with 2019
+entity ent is
+end entity;
+  `end
+",
+        );
+
+        let mut diagnostics = Vec::new();
+        let design_file = parser("2019").parse_design_source(code.source(), &mut diagnostics);
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(design_file.design_units.len(), 1);
+        assert_eq!(
+            design_file.design_units[0].ident().pos,
+            code.s("ent", 2).pos()
+        );
+    }
+
+    #[test]
+    fn directive_errors() {
+        let code = Code::new(
+            "
+`if UNKNOWN = \"1\" then
+`end if
+`warning \"not yet supported\"
+`end
+`if VHDL_VERSION = \"2019\" then
+",
+        );
+
+        let (_, diagnostics) = unit_names(&parser("2019"), &code);
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("UNKNOWN"),
+                    "Undefined conditional analysis identifier 'UNKNOWN'",
+                ),
+                Diagnostic::warning(
+                    code.s1("`warning \"not yet supported\""),
+                    "not yet supported",
+                ),
+                Diagnostic::error(code.s("`end", 2), "`end directive without matching `if"),
+                Diagnostic::error(
+                    code.s1("`if VHDL_VERSION = \"2019\" then"),
+                    "Missing `end for `if directive",
+                ),
+            ]
+            .into_iter()
+            .map(|diagnostic| diagnostic.with_origin(Origin::Syntax))
+            .collect(),
+        );
+    }
+}
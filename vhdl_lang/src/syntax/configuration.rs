@@ -309,6 +309,7 @@ pub fn parse_configuration_declaration(
         decl,
         vunit_bind_inds,
         block_config,
+        end_ident_pos: end_ident.map(|ident| ident.pos),
     })
 }
 
@@ -375,7 +376,8 @@ end;
                     block_spec: code.s1("rtl(0)").name(),
                     use_clauses: vec![],
                     items: vec![],
-                }
+                },
+                end_ident_pos: None,
             }
         );
     }
@@ -402,7 +404,8 @@ end configuration cfg;
                     block_spec: code.s1("rtl(0)").name(),
                     use_clauses: vec![],
                     items: vec![],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -433,7 +436,8 @@ end configuration cfg;
                     block_spec: code.s1("rtl(0)").name(),
                     use_clauses: vec![],
                     items: vec![],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -466,7 +470,8 @@ end configuration cfg;
                     block_spec: code.s1("rtl(0)").name(),
                     use_clauses: vec![],
                     items: vec![],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -493,7 +498,8 @@ end configuration cfg;
                     block_spec: code.s1("rtl(0)").name(),
                     use_clauses: vec![],
                     items: vec![],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -535,7 +541,8 @@ end configuration cfg;
                             items: vec![],
                         })
                     ],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -580,7 +587,8 @@ end configuration cfg;
                             items: vec![],
                         }),
                     }),],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -636,7 +644,8 @@ end configuration cfg;
                             items: vec![],
                         }),
                     }),],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -683,7 +692,8 @@ end configuration cfg;
                         vunit_bind_inds: Vec::new(),
                         block_config: None,
                     }),],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
@@ -761,7 +771,8 @@ end configuration cfg;
                             block_config: None,
                         })
                     ],
-                }
+                },
+                end_ident_pos: Some(code.s("cfg", 2).pos()),
             }
         );
     }
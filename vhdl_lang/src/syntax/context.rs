@@ -117,6 +117,7 @@ pub fn parse_context(
         Ok(DeclarationOrReference::Declaration(ContextDeclaration {
             ident,
             items,
+            end_ident_pos: end_ident.map(|ident| ident.pos),
         }))
     } else {
         // Context reference
@@ -226,33 +227,71 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_context_reference_round_trip() {
+        let code = Code::new("context work.foo, lib.bar;");
+        let reference = match code.with_stream_no_diagnostics(parse_context) {
+            DeclarationOrReference::Reference(reference) => reference.item,
+            DeclarationOrReference::Declaration(..) => panic!("Expected context reference"),
+        };
+        assert_eq!(reference.to_string(), "context work.foo, lib.bar");
+
+        let code = Code::new(&format!("{};", reference));
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_context),
+            DeclarationOrReference::Reference(WithPos::new(
+                ContextReference {
+                    name_list: vec![code.s1("work.foo").name(), code.s1("lib.bar").name()]
+                },
+                code
+            ))
+        );
+    }
+
     #[test]
     fn test_context_clause() {
         let variants = vec![
-            &"\
+            (
+                &"\
 context ident is
 end;
 ",
-            &"\
+                false,
+            ),
+            (
+                &"\
 context ident is
 end context;
 ",
-            &"\
+                false,
+            ),
+            (
+                &"\
 context ident is
 end ident;
 ",
-            &"\
+                true,
+            ),
+            (
+                &"\
 context ident is
 end context ident;
 ",
+                true,
+            ),
         ];
-        for variant in variants {
+        for (variant, has_end_ident) in variants {
             let code = Code::new(variant);
             assert_eq!(
                 code.with_stream_no_diagnostics(parse_context),
                 DeclarationOrReference::Declaration(ContextDeclaration {
                     ident: code.s1("ident").ident(),
-                    items: vec![]
+                    items: vec![],
+                    end_ident_pos: if has_end_ident {
+                        Some(code.s("ident", 2).pos())
+                    } else {
+                        None
+                    },
                 })
             );
         }
@@ -278,7 +317,8 @@ end context ident2;
             context,
             DeclarationOrReference::Declaration(ContextDeclaration {
                 ident: code.s1("ident").ident(),
-                items: vec![]
+                items: vec![],
+                end_ident_pos: Some(code.s1("ident2").pos()),
             })
         );
     }
@@ -317,7 +357,8 @@ end context;
                         }),
                         code.s1("context foo.ctx;")
                     ),
-                ]
+                ],
+                end_ident_pos: None,
             })
         )
     }
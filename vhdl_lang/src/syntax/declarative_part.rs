@@ -8,15 +8,17 @@ use super::alias_declaration::parse_alias_declaration;
 use super::attributes::parse_attribute;
 use super::common::ParseResult;
 use super::component_declaration::parse_component_declaration;
-use super::configuration::parse_configuration_specification;
+use super::configuration::{parse_configuration_declaration, parse_configuration_specification};
 use super::context::parse_use_clause;
+use super::design_unit::{parse_architecture_body, parse_entity_declaration};
+use super::group_declaration::parse_group;
 use super::names::{parse_association_list, parse_selected_name};
 use super::object_declaration::{parse_file_declaration, parse_object_declaration};
 use super::subprogram::parse_subprogram;
 use super::tokens::{Kind::*, *};
 use super::type_declaration::parse_type_declaration;
 use crate::ast::{ContextClause, Declaration, PackageInstantiation};
-use crate::data::DiagnosticHandler;
+use crate::data::{Diagnostic, DiagnosticHandler, Origin};
 
 pub fn parse_package_instantiation(stream: &mut TokenStream) -> ParseResult<PackageInstantiation> {
     stream.expect_kind(Package)?;
@@ -50,13 +52,15 @@ pub fn is_declarative_part(stream: &mut TokenStream, begin_is_end: bool) -> Pars
 fn check_declarative_part(token: &Token, may_end: bool, may_begin: bool) -> ParseResult<()> {
     match token.kind {
         Use | Type | Subtype | Shared | Constant | Signal | Variable | File | Component
-        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For => Ok(()),
+        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For | Group => {
+            Ok(())
+        }
         Begin if may_begin => Ok(()),
         End if may_end => Ok(()),
         _ => {
             let decl_kinds = [
                 Use, Type, Subtype, Shared, Constant, Signal, Variable, File, Component, Attribute,
-                Alias, Impure, Pure, Function, Procedure, Package, For,
+                Alias, Impure, Pure, Function, Procedure, Package, For, Group,
             ];
 
             Err(token.kinds_error(&decl_kinds))
@@ -78,13 +82,34 @@ pub fn parse_declarative_part(
 pub fn parse_declarative_part_leave_end_token(
     stream: &mut TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Vec<Declaration>> {
+    parse_declarations(stream, diagnostics, false)
+}
+
+/// The declarative part of a package or package body
+/// A misplaced library unit is reported and skipped as a whole
+pub fn parse_package_declarative_part(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<Vec<Declaration>> {
+    let decl = parse_declarations(stream, diagnostics, true)?;
+    stream.expect_kind(End).log(diagnostics);
+    Ok(decl)
+}
+
+fn parse_declarations(
+    stream: &mut TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+    in_package: bool,
 ) -> ParseResult<Vec<Declaration>> {
     let mut declarations: Vec<Declaration> = Vec::new();
 
     fn is_recover_token(kind: Kind) -> bool {
         match kind {
             Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For
-            | File | Shared | Constant | Signal | Variable | Attribute | Use | Alias => true,
+            | File | Shared | Constant | Signal | Variable | Attribute | Use | Alias | Group => {
+                true
+            }
             _ => false,
         }
     };
@@ -115,7 +140,7 @@ pub fn parse_declarative_part_leave_end_token(
                         .map(|decls| decls.into_iter().map(Declaration::File).collect()),
                     Shared | Constant | Signal | Variable => parse_object_declaration(stream)
                         .map(|decls| decls.into_iter().map(Declaration::Object).collect()),
-                    Attribute => parse_attribute(stream)
+                    Attribute => parse_attribute(stream, diagnostics)
                         .map(|decls| decls.into_iter().map(Declaration::Attribute).collect()),
                     _ => unreachable!(),
                 };
@@ -128,10 +153,11 @@ pub fn parse_declarative_part_leave_end_token(
                 }
             }
 
-            Use | Alias => {
+            Use | Alias | Group => {
                 let decl: ParseResult<Declaration> = match token.kind {
                     Use => parse_use_clause(stream).map(Declaration::Use),
                     Alias => parse_alias_declaration(stream).map(Declaration::Alias),
+                    Group => parse_group(stream),
                     _ => unreachable!(),
                 };
                 match decl.or_recover_until(stream, diagnostics, is_recover_token) {
@@ -143,10 +169,32 @@ pub fn parse_declarative_part_leave_end_token(
                 }
             }
 
+            Entity | Architecture | Configuration if in_package => {
+                diagnostics.push(
+                    Diagnostic::error(
+                        &token.pos,
+                        "Entity/architecture cannot be declared inside a package",
+                    )
+                    .with_origin(Origin::Semantic),
+                );
+                let unit = match token.kind {
+                    Entity => parse_entity_declaration(stream, diagnostics).map(|_| ()),
+                    Architecture => parse_architecture_body(stream, diagnostics).map(|_| ()),
+                    Configuration => {
+                        parse_configuration_declaration(stream, diagnostics).map(|_| ())
+                    }
+                    _ => unreachable!(),
+                };
+                if let Err(err) = unit {
+                    diagnostics.push(err);
+                    stream.skip_until(is_recover_token)?;
+                }
+            }
+
             _ => {
                 diagnostics.push(token.kinds_error(&[
                     Type, Subtype, Component, Impure, Pure, Function, Procedure, Package, For,
-                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias,
+                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias, Group,
                 ]));
                 stream.skip_until(is_recover_token)?;
                 continue;
@@ -224,6 +272,7 @@ constant x: natural := 5;
                 class: ObjectClass::Constant,
                 ident: code.s1("x").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("5").expr())
             })])
         );
@@ -235,7 +284,7 @@ constant x: natural := 5;
                 "Expected 'type', 'subtype', 'component', 'impure', 'pure', \
                  'function', 'procedure', 'package', 'for', 'file', \
                  'shared', 'constant', 'signal', 'variable', 'attribute', \
-                 'use' or 'alias'"
+                 'use', 'alias' or 'group'"
             )]
         );
     }
@@ -15,7 +15,8 @@ use super::context::{
     parse_context, parse_library_clause, parse_use_clause, DeclarationOrReference,
 };
 use super::declarative_part::{
-    parse_declarative_part, parse_declarative_part_leave_end_token, parse_package_instantiation,
+    parse_declarative_part, parse_declarative_part_leave_end_token, parse_package_declarative_part,
+    parse_package_instantiation,
 };
 use super::interface_declaration::parse_generic_interface_list;
 use crate::ast::*;
@@ -56,6 +57,7 @@ pub fn parse_entity_declaration(
         port_clause,
         decl,
         statements,
+        end_ident_pos: end_ident.map(|ident| ident.pos),
     })
 }
 
@@ -88,6 +90,7 @@ pub fn parse_architecture_body(
         entity_name: entity_name.into_ref(),
         decl,
         statements,
+        end_ident_pos: end_ident.map(|ident| ident.pos),
     })
 }
 
@@ -109,7 +112,7 @@ pub fn parse_package_declaration(
             None
         }
     };
-    let decl = parse_declarative_part(stream, diagnostics, false)?;
+    let decl = parse_package_declarative_part(stream, diagnostics)?;
     stream.pop_if_kind(Package)?;
     let end_ident = stream.pop_optional_ident()?;
     if let Some(diagnostic) = error_on_end_identifier_mismatch(&ident, &end_ident) {
@@ -122,6 +125,7 @@ pub fn parse_package_declaration(
         ident,
         generic_clause,
         decl,
+        end_ident_pos: end_ident.map(|ident| ident.pos),
     })
 }
 
@@ -135,7 +139,8 @@ pub fn parse_package_body(
     let ident = stream.expect_ident()?;
 
     stream.expect_kind(Is)?;
-    let decl = parse_declarative_part(stream, diagnostics, false)?;
+    let decl = parse_package_declarative_part(stream, diagnostics)?;
+    check_package_body_declarations(&decl, diagnostics);
     if stream.skip_if_kind(Package)? {
         stream.expect_kind(Body)?;
     }
@@ -149,9 +154,31 @@ pub fn parse_package_body(
         context_clause: ContextClause::default(),
         ident: ident.into_ref(),
         decl,
+        end_ident_pos: end_ident.map(|ident| ident.pos),
     })
 }
 
+/// LRM 4.8 Signals, components and configuration specifications
+/// are not package body declarative items
+fn check_package_body_declarations(decls: &[Declaration], diagnostics: &mut dyn DiagnosticHandler) {
+    for decl in decls.iter() {
+        let pos = match decl {
+            Declaration::Object(ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ref ident,
+                ..
+            }) => &ident.pos,
+            Declaration::Component(ref component) => &component.ident.pos,
+            Declaration::Configuration(ref config) => &config.spec.component_name.pos,
+            _ => continue,
+        };
+        diagnostics.push(
+            Diagnostic::error(pos, "Declaration not allowed in package body")
+                .with_origin(Origin::Semantic),
+        );
+    }
+}
+
 fn take_context_clause(context_clause: &mut ContextClause) -> ContextClause {
     std::mem::take(context_clause)
 }
@@ -309,7 +336,7 @@ mod tests {
     }
 
     /// An simple entity with only a name
-    fn simple_entity(ident: Ident) -> AnyDesignUnit {
+    fn simple_entity(ident: Ident, end_ident_pos: Option<SrcPos>) -> AnyDesignUnit {
         AnyDesignUnit::Primary(AnyPrimaryUnit::Entity(EntityDeclaration {
             context_clause: ContextClause::default(),
             ident,
@@ -317,6 +344,7 @@ mod tests {
             port_clause: None,
             decl: vec![],
             statements: vec![],
+            end_ident_pos,
         }))
     }
 
@@ -330,7 +358,7 @@ end entity;
         );
         assert_eq!(
             design_file.design_units,
-            [simple_entity(code.s1("myent").ident())]
+            [simple_entity(code.s1("myent").ident(), None)]
         );
 
         let (code, design_file) = parse_ok(
@@ -341,7 +369,10 @@ end entity myent;
         );
         assert_eq!(
             design_file.design_units,
-            [simple_entity(code.s1("myent").ident())]
+            [simple_entity(
+                code.s1("myent").ident(),
+                Some(code.s("myent", 2).pos())
+            )]
         );
     }
 
@@ -363,6 +394,7 @@ end entity;
                 port_clause: None,
                 decl: vec![],
                 statements: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -390,6 +422,7 @@ end entity;
                 port_clause: None,
                 decl: vec![],
                 statements: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -412,6 +445,7 @@ end entity;
                 port_clause: Some(vec![]),
                 decl: vec![],
                 statements: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -434,6 +468,7 @@ end entity;
                 port_clause: None,
                 decl: vec![],
                 statements: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -456,6 +491,7 @@ end entity;
                 port_clause: None,
                 decl: code.s1("constant foo : natural := 0;").declarative_part(),
                 statements: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -479,6 +515,7 @@ end entity;
                 port_clause: None,
                 decl: vec![],
                 statements: vec![code.s1("check(clk, valid);").concurrent_statement()],
+                end_ident_pos: None,
             }
         );
     }
@@ -503,22 +540,27 @@ end;
         assert_eq!(
             design_file.design_units,
             [
-                simple_entity(code.s1("myent").ident()),
-                simple_entity(code.s1("myent2").ident()),
-                simple_entity(code.s1("myent3").ident()),
-                simple_entity(code.s1("myent4").ident())
+                simple_entity(code.s1("myent").ident(), None),
+                simple_entity(code.s1("myent2").ident(), Some(code.s("myent2", 2).pos())),
+                simple_entity(code.s1("myent3").ident(), Some(code.s("myent3", 2).pos())),
+                simple_entity(code.s1("myent4").ident(), None)
             ]
         );
     }
 
     // An simple entity with only a name
-    fn simple_architecture(ident: Ident, entity_name: Ident) -> AnyDesignUnit {
+    fn simple_architecture(
+        ident: Ident,
+        entity_name: Ident,
+        end_ident_pos: Option<SrcPos>,
+    ) -> AnyDesignUnit {
         AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(ArchitectureBody {
             context_clause: ContextClause::default(),
             ident,
             entity_name: entity_name.into_ref(),
             decl: Vec::new(),
             statements: vec![],
+            end_ident_pos,
         }))
     }
 
@@ -535,7 +577,8 @@ end architecture;
             design_file.design_units,
             [simple_architecture(
                 code.s1("arch_name").ident(),
-                code.s1("myent").ident()
+                code.s1("myent").ident(),
+                None
             )]
         );
     }
@@ -553,7 +596,8 @@ end architecture arch_name;
             design_file.design_units,
             [simple_architecture(
                 code.s1("arch_name").ident(),
-                code.s1("myent").ident()
+                code.s1("myent").ident(),
+                Some(code.s("arch_name", 2).pos())
             )]
         );
     }
@@ -571,7 +615,8 @@ end;
             design_file.design_units,
             [simple_architecture(
                 code.s1("arch_name").ident(),
-                code.s1("myent").ident()
+                code.s1("myent").ident(),
+                None
             )]
         );
     }
@@ -591,6 +636,7 @@ end package;
                 ident: code.s1("pkg_name").ident(),
                 generic_clause: None,
                 decl: vec![],
+                end_ident_pos: None,
             }
         );
     }
@@ -617,6 +663,7 @@ end package;
   constant bar : natural := 0;
 ")
                     .declarative_part(),
+                end_ident_pos: None,
             }
         );
     }
@@ -642,11 +689,95 @@ end package;
                     code.s1("type foo").generic(),
                     code.s1("type bar").generic()
                 ]),
-                decl: vec![]
+                decl: vec![],
+                end_ident_pos: None,
             }
         );
     }
 
+    #[test]
+    fn test_package_body_with_subprogram_bodies() {
+        let code = Code::new(
+            "
+package body pkg_name is
+  constant bar : natural := 0;
+  shared variable var : natural;
+
+  function fun return natural is
+  begin
+    return bar;
+  end function;
+
+  procedure proc is
+  begin
+  end procedure;
+end package body;
+",
+        );
+        let package_body = code.with_stream_no_diagnostics(parse_package_body);
+        assert_eq!(package_body.decl.len(), 4);
+    }
+
+    #[test]
+    fn error_on_illegal_package_body_declarations() {
+        let code = Code::new(
+            "
+package body pkg_name is
+  constant bar : natural := 0;
+  signal sig : natural;
+  component comp is
+  end component;
+end package body;
+",
+        );
+        let (package_body, diagnostics) = code.with_stream_diagnostics(parse_package_body);
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::error(code.s("sig", 2), "Declaration not allowed in package body")
+                    .with_origin(Origin::Semantic),
+                Diagnostic::error(code.s("comp", 2), "Declaration not allowed in package body")
+                    .with_origin(Origin::Semantic),
+            ],
+        );
+        assert_eq!(package_body.decl.len(), 3);
+    }
+
+    #[test]
+    fn error_on_library_unit_inside_package() {
+        let code = Code::new(
+            "
+package pkg_name is
+  constant foo : natural := 0;
+  entity ent is
+    port (clk : in bit);
+  end entity;
+  architecture a of ent is
+  begin
+  end architecture;
+  constant bar : natural := 1;
+end package;
+",
+        );
+        let (package, diagnostics) = code.with_stream_diagnostics(parse_package_declaration);
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::error(
+                    code.s1("entity"),
+                    "Entity/architecture cannot be declared inside a package",
+                )
+                .with_origin(Origin::Semantic),
+                Diagnostic::error(
+                    code.s1("architecture"),
+                    "Entity/architecture cannot be declared inside a package",
+                )
+                .with_origin(Origin::Semantic),
+            ],
+        );
+        assert_eq!(package.decl.len(), 2);
+    }
+
     #[test]
     fn context_clause_associated_with_design_units() {
         let (code, design_file) = parse_ok(
@@ -676,12 +807,55 @@ end entity;
                         port_clause: None,
                         decl: vec![],
                         statements: vec![],
+                        end_ident_pos: None,
                     }
                 ))]
             }
         );
     }
 
+    #[test]
+    fn context_clauses_of_design_unit_in_order() {
+        let (code, design_file) = parse_ok(
+            "
+library ieee;
+use ieee.std_logic_1164.all;
+
+architecture arch of ent is
+begin
+end architecture;
+",
+        );
+        assert_eq!(
+            design_file.design_units[0].context_clauses(),
+            &[
+                code.s1("library ieee;")
+                    .library_clause()
+                    .map_into(ContextItem::Library),
+                code.s1("use ieee.std_logic_1164.all;")
+                    .use_clause()
+                    .map_into(ContextItem::Use),
+            ][..]
+        );
+    }
+
+    #[test]
+    fn declaration_for_trailing_end_label() {
+        let (code, design_file) = parse_ok(
+            "
+architecture rtl of ent is
+begin
+end architecture rtl;
+",
+        );
+        let unit = &design_file.design_units[0];
+        assert_eq!(
+            declaration_for_end_label(unit, &code.s("rtl", 2).pos()),
+            Some(code.s1("rtl").pos())
+        );
+        assert_eq!(declaration_for_end_label(unit, &code.s1("ent").pos()), None);
+    }
+
     #[test]
     fn warning_on_orphan_context_clause() {
         let code = Code::new(
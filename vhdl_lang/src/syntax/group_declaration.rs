@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use super::attributes::parse_entity_class;
+use super::common::ParseResult;
+use super::names::{parse_name, parse_selected_name};
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::{Declaration, GroupDeclaration, GroupTemplateDeclaration};
+
+/// LRM 6.9 Group template declarations and LRM 6.10 Group declarations
+pub fn parse_group(stream: &mut TokenStream) -> ParseResult<Declaration> {
+    stream.expect_kind(Group)?;
+    let ident = stream.expect_ident()?;
+    let token = stream.expect()?;
+
+    Ok(try_token_kind!(
+        token,
+        Is => {
+            stream.expect_kind(LeftPar)?;
+            let mut entity_classes = Vec::new();
+            let mut is_repeated = false;
+            loop {
+                entity_classes.push(parse_entity_class(stream)?);
+                let token = stream.expect()?;
+                try_token_kind!(
+                    token,
+                    Comma => {},
+                    BOX => {
+                        is_repeated = true;
+                        stream.expect_kind(RightPar)?;
+                        break;
+                    },
+                    RightPar => break
+                );
+            }
+            stream.expect_kind(SemiColon)?;
+
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident,
+                entity_classes,
+                is_repeated,
+            })
+        },
+        Colon => {
+            let template_name = parse_selected_name(stream)?;
+            stream.expect_kind(LeftPar)?;
+            let mut constituents = Vec::new();
+            loop {
+                constituents.push(parse_name(stream)?);
+                let token = stream.expect()?;
+                try_token_kind!(
+                    token,
+                    Comma => {},
+                    RightPar => break
+                );
+            }
+            stream.expect_kind(SemiColon)?;
+
+            Declaration::Group(GroupDeclaration {
+                ident,
+                template_name,
+                constituents,
+            })
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::EntityClass;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn parse_group_template_declaration() {
+        let code = Code::new("group pin2pin is (signal, signal);");
+        assert_eq!(
+            code.with_stream(parse_group),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("pin2pin").ident(),
+                entity_classes: vec![EntityClass::Signal, EntityClass::Signal],
+                is_repeated: false,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_repeated_group_template_declaration() {
+        let code = Code::new("group resource is (label <>);");
+        assert_eq!(
+            code.with_stream(parse_group),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("resource").ident(),
+                entity_classes: vec![EntityClass::Label],
+                is_repeated: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_group_declaration() {
+        let code = Code::new("group path : lib.pkg.pin2pin (sig1, sig2);");
+        assert_eq!(
+            code.with_stream(parse_group),
+            Declaration::Group(GroupDeclaration {
+                ident: code.s1("path").ident(),
+                template_name: code.s1("lib.pkg.pin2pin").selected_name(),
+                constituents: vec![code.s1("sig1").name(), code.s1("sig2").name()],
+            })
+        );
+    }
+}
@@ -10,6 +10,7 @@ use super::object_declaration::{parse_file_declaration_no_semi, parse_optional_a
 use super::subprogram::parse_subprogram_declaration_no_semi;
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Kind::*, *};
+use super::type_declaration::parse_array_type_definition_no_semi;
 /// LRM 6.5 Interface declarations
 use crate::ast::*;
 use crate::data::*;
@@ -225,7 +226,13 @@ fn parse_interface_declaration(
         Type => {
             stream.move_after(&token);
             let ident = stream.expect_ident()?;
-            Ok(vec![InterfaceDeclaration::Type(ident)])
+            let def = if stream.skip_if_kind(Is)? {
+                stream.expect_kind(Array)?;
+                Some(parse_array_type_definition_no_semi(stream)?)
+            } else {
+                None
+            };
+            Ok(vec![InterfaceDeclaration::Type(InterfaceTypeDeclaration { ident, def })])
         },
         Function | Procedure | Impure | Pure => {
             let decl = parse_subprogram_declaration_no_semi(stream, diagnostics)?;
@@ -572,6 +579,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_port_with_linkage_mode() {
+        let code = Code::new("signal foo : linkage std_logic");
+        assert_eq!(
+            code.with_stream(parse_port),
+            InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                mode: Mode::Linkage,
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").ident(),
+                subtype_indication: code.s1("std_logic").subtype_indication(),
+                expression: None
+            })
+        );
+    }
+
     #[test]
     fn parse_generic_non_in_mode_error() {
         let code = Code::new("foo : out boolean");
@@ -745,7 +767,27 @@ bar : natural)",
         let code = Code::new("type name");
         assert_eq!(
             code.with_stream(parse_generic),
-            InterfaceDeclaration::Type(code.s1("name").ident())
+            InterfaceDeclaration::Type(InterfaceTypeDeclaration {
+                ident: code.s1("name").ident(),
+                def: None
+            })
+        );
+    }
+
+    #[test]
+    fn parses_interface_array_type() {
+        let code = Code::new("type name is array (natural range <>) of bit");
+        assert_eq!(
+            code.with_stream(parse_generic),
+            InterfaceDeclaration::Type(InterfaceTypeDeclaration {
+                ident: code.s1("name").ident(),
+                def: Some(TypeDefinition::Array(
+                    vec![ArrayIndex::IndexSubtypeDefintion(
+                        code.s1("natural").selected_name()
+                    )],
+                    code.s1("bit").subtype_indication()
+                ))
+            })
         );
     }
 
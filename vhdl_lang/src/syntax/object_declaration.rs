@@ -24,6 +24,16 @@ pub fn parse_optional_assignment(
     }
 }
 
+fn parse_optional_signal_kind(stream: &mut TokenStream) -> ParseResult<Option<SignalKind>> {
+    if stream.skip_if_kind(Register)? {
+        Ok(Some(SignalKind::Register))
+    } else if stream.skip_if_kind(Bus)? {
+        Ok(Some(SignalKind::Bus))
+    } else {
+        Ok(None)
+    }
+}
+
 fn parse_object_declaration_kind(
     stream: &mut TokenStream,
     class: ObjectClass,
@@ -31,6 +41,11 @@ fn parse_object_declaration_kind(
     let idents = parse_identifier_list(stream)?;
     stream.expect_kind(Colon)?;
     let subtype = parse_subtype_indication(stream)?;
+    let signal_kind = if class == ObjectClass::Signal {
+        parse_optional_signal_kind(stream)?
+    } else {
+        None
+    };
     let opt_expression = parse_optional_assignment(stream)?;
 
     Ok(idents
@@ -39,6 +54,7 @@ fn parse_object_declaration_kind(
             class,
             ident,
             subtype_indication: subtype.clone(),
+            signal_kind,
             expression: opt_expression.clone(),
         })
         .collect())
@@ -115,6 +131,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -129,11 +146,42 @@ mod tests {
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
+                expression: None
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_signal_with_register_kind() {
+        let code = Code::new("signal foo : std_logic register;");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").ident(),
+                subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: Some(SignalKind::Register),
                 expression: None
             }]
         );
     }
 
+    #[test]
+    fn parses_signal_with_bus_kind() {
+        let code = Code::new("signal foo : std_logic bus := '0';");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").ident(),
+                subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: Some(SignalKind::Bus),
+                expression: Some(code.s1("'0'").expr())
+            }]
+        );
+    }
+
     #[test]
     fn parses_variable() {
         let code = Code::new("variable foo : natural;");
@@ -143,6 +191,7 @@ mod tests {
                 class: ObjectClass::Variable,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -157,6 +206,7 @@ mod tests {
                 class: ObjectClass::SharedVariable,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -213,6 +263,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr())
             }]
         );
@@ -227,12 +278,14 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr()),
             },
             ObjectDeclaration {
                 class: ObjectClass::Constant,
                 ident: code.s1("bar").ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr()),
             },
         ];
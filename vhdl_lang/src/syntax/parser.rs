@@ -4,6 +4,7 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
+use super::conditional_analysis::{evaluate_tool_directives, ConditionalIdentifiers};
 use super::design_unit::parse_design_file;
 use super::tokens::{Symbols, TokenStream, Tokenizer};
 use crate::ast::DesignFile;
@@ -14,6 +15,8 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct VHDLParser {
     pub symbols: Arc<Symbols>,
+    /// Identifiers of conditional analysis, tool directives are not evaluated when None
+    conditional_identifiers: Option<ConditionalIdentifiers>,
 }
 
 pub type ParserResult = Result<(Source, DesignFile), io::Error>;
@@ -23,13 +26,37 @@ impl VHDLParser {
         self.symbols.symtab().insert(name)
     }
 
+    /// Evaluate conditional analysis tool directives such as `if against the given
+    /// identifier values before parsing, identifier names are case insensitive
+    pub fn enable_conditional_analysis(
+        &mut self,
+        identifiers: impl IntoIterator<Item = (String, String)>,
+    ) {
+        self.conditional_identifiers = Some(
+            identifiers
+                .into_iter()
+                .map(|(name, value)| (normalize_identifier(&name), value))
+                .collect(),
+        );
+    }
+
     pub fn parse_design_source(
         &self,
         source: &Source,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> DesignFile {
-        let contents = source.contents();
-        let tokenizer = Tokenizer::new(&self.symbols, &source, ContentReader::new(&contents));
+        let mut syntax_diagnostics = SyntaxDiagnostics { diagnostics };
+        let diagnostics: &mut dyn DiagnosticHandler = &mut syntax_diagnostics;
+        let source_contents = source.contents();
+        let preprocessed;
+        let contents = if let Some(ref identifiers) = self.conditional_identifiers {
+            preprocessed =
+                evaluate_tool_directives(source, &source_contents, identifiers, diagnostics);
+            &preprocessed
+        } else {
+            &*source_contents
+        };
+        let tokenizer = Tokenizer::new(&self.symbols, &source, ContentReader::new(contents));
         let mut stream = TokenStream::new(tokenizer);
 
         match parse_design_file(&mut stream, diagnostics) {
@@ -51,3 +78,16 @@ impl VHDLParser {
         Ok((source, design_file))
     }
 }
+
+/// Marks the diagnostics from the parser as syntax diagnostics
+/// unless they were explicitly reported as semantic errors
+struct SyntaxDiagnostics<'a> {
+    diagnostics: &'a mut dyn DiagnosticHandler,
+}
+
+impl<'a> DiagnosticHandler for SyntaxDiagnostics<'a> {
+    fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics
+            .push(diagnostic.with_default_origin(Origin::Syntax));
+    }
+}
@@ -150,6 +150,7 @@ fn parse_case_statement_known_keyword(
     stream.expect_kind(Is)?;
     stream.expect_kind(When)?;
     let mut alternatives = Vec::new();
+    let end_pos;
 
     loop {
         let choices = parse_choices(stream)?;
@@ -166,6 +167,7 @@ fn parse_case_statement_known_keyword(
                 continue;
             },
             End => {
+                end_pos = end_token.pos.clone();
                 stream.expect_kind(Case)?;
                 if is_matching {
                     stream.expect_kind(Que)?;
@@ -183,6 +185,7 @@ fn parse_case_statement_known_keyword(
         is_matching,
         expression,
         alternatives,
+        end_pos,
     })
 }
 
@@ -1421,6 +1424,7 @@ end case;
                             ]
                         }
                     ],
+                    end_pos: code.s1("end case").s1("end").pos(),
                 })
             )
         );
@@ -1446,6 +1450,7 @@ end case?;
                         choices: code.s1("others").choices(),
                         item: vec![code.s1("null;").sequential_statement(),]
                     }],
+                    end_pos: code.s1("end case").s1("end").pos(),
                 })
             )
         );
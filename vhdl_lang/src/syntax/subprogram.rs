@@ -7,7 +7,7 @@
 use super::common::ParseResult;
 use super::declarative_part::parse_declarative_part;
 use super::interface_declaration::parse_parameter_interface_list;
-use super::names::parse_selected_name;
+use super::names::{parse_association_list, parse_selected_name};
 use super::sequential_statement::parse_labeled_sequential_statements;
 use super::tokens::{Kind::*, TokenStream};
 use crate::ast::*;
@@ -173,10 +173,69 @@ pub fn parse_subprogram_body(
     })
 }
 
+fn is_subprogram_instantiation(stream: &mut TokenStream) -> ParseResult<bool> {
+    for kind in [Procedure, Function].iter() {
+        for designator in [Identifier, StringLiteral].iter() {
+            if stream.next_kinds_are(&[*kind, *designator, Is, New])? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// LRM 4.4 Subprogram instantiation declarations
+pub fn parse_subprogram_instantiation(
+    stream: &mut TokenStream,
+) -> ParseResult<SubprogramInstantiation> {
+    let token = stream.expect()?;
+    let kind = try_token_kind!(
+        token,
+        Procedure => SubprogramKind::Procedure,
+        Function => SubprogramKind::Function
+    );
+    let designator = parse_designator(stream)?;
+    stream.expect_kind(Is)?;
+    stream.expect_kind(New)?;
+    let subprogram_name = parse_selected_name(stream)?;
+
+    let signature = {
+        if stream.peek_kind()? == Some(LeftSquare) {
+            Some(parse_signature(stream)?)
+        } else {
+            None
+        }
+    };
+
+    let generic_map = {
+        if stream.skip_if_kind(Generic)? {
+            stream.expect_kind(Map)?;
+            Some(parse_association_list(stream)?)
+        } else {
+            None
+        }
+    };
+    stream.expect_kind(SemiColon)?;
+
+    Ok(SubprogramInstantiation {
+        kind,
+        designator,
+        subprogram_name,
+        signature,
+        generic_map,
+    })
+}
+
 pub fn parse_subprogram(
     stream: &mut TokenStream,
     diagnostics: &mut dyn DiagnosticHandler,
 ) -> ParseResult<Declaration> {
+    if is_subprogram_instantiation(stream)? {
+        return Ok(Declaration::SubprogramInstantiation(
+            parse_subprogram_instantiation(stream)?,
+        ));
+    }
+
     let specification = parse_subprogram_declaration_no_semi(stream, diagnostics)?;
     match_token_kind!(
         stream.expect()?,
@@ -236,6 +295,50 @@ function foo return lib.foo.natural;
         );
     }
 
+    #[test]
+    pub fn parses_procedure_instantiation() {
+        let code = Code::new(
+            "\
+procedure proc is new lib.pkg.gen_proc [natural] generic map (width => 8);
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram),
+            Declaration::SubprogramInstantiation(SubprogramInstantiation {
+                kind: SubprogramKind::Procedure,
+                designator: code
+                    .s("proc", 2)
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier),
+                subprogram_name: code.s1("lib.pkg.gen_proc").selected_name(),
+                signature: Some(code.s1("[natural]").signature()),
+                generic_map: Some(code.s1("(width => 8)").association_list()),
+            })
+        );
+    }
+
+    #[test]
+    pub fn parses_function_instantiation() {
+        let code = Code::new(
+            "\
+function fun is new gen_fun;
+",
+        );
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_subprogram),
+            Declaration::SubprogramInstantiation(SubprogramInstantiation {
+                kind: SubprogramKind::Function,
+                designator: code
+                    .s("fun", 2)
+                    .ident()
+                    .map_into(SubprogramDesignator::Identifier),
+                subprogram_name: code.s1("gen_fun").selected_name(),
+                signature: None,
+                generic_map: None,
+            })
+        );
+    }
+
     #[test]
     pub fn parses_function_specification_operator() {
         let code = Code::new(
@@ -10,7 +10,9 @@ use super::context::{parse_library_clause, parse_use_clause};
 use super::declarative_part::parse_declarative_part_leave_end_token;
 use super::design_unit::parse_design_file;
 use super::expression::{parse_aggregate, parse_choices, parse_expression};
-use super::interface_declaration::{parse_generic, parse_parameter, parse_port};
+use super::interface_declaration::{
+    parse_generic, parse_parameter, parse_port, parse_port_interface_list,
+};
 use super::names::{parse_association_list, parse_designator, parse_name, parse_selected_name};
 use super::range::{parse_discrete_range, parse_range};
 use super::sequential_statement::parse_sequential_statement;
@@ -342,6 +344,10 @@ impl Code {
         self.parse_ok(parse_port)
     }
 
+    pub fn port_list(&self) -> Vec<InterfaceDeclaration> {
+        self.with_stream_no_diagnostics(parse_port_interface_list)
+    }
+
     pub fn generic(&self) -> InterfaceDeclaration {
         self.parse_ok(parse_generic)
     }
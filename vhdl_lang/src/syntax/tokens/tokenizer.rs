@@ -73,6 +73,8 @@ pub enum Kind {
     Buffer,
     Linkage,
     Signal,
+    Bus,
+    Register,
     Constant,
     Variable,
     File,
@@ -93,6 +95,7 @@ pub enum Kind {
     Function,
     Procedure,
     Vunit,
+    Group,
 
     // Unary operators
     Abs,
@@ -277,6 +280,8 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Buffer => &"buffer",
         Linkage => &"linkage",
         Signal => &"signal",
+        Bus => &"bus",
+        Register => &"register",
         Constant => &"constant",
         Variable => &"variable",
         File => &"file",
@@ -297,6 +302,7 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Function => &"function",
         Procedure => &"procedure",
         Vunit => &"vunit",
+        Group => &"group",
 
         // Unary operators
         Abs => &"abs",
@@ -1237,6 +1243,8 @@ impl std::default::Default for Symbols {
             ("buffer", Buffer),
             ("linkage", Linkage),
             ("signal", Signal),
+            ("bus", Bus),
+            ("register", Register),
             ("constant", Constant),
             ("variable", Variable),
             ("file", File),
@@ -1273,6 +1281,7 @@ impl std::default::Default for Symbols {
             ("mod", Mod),
             ("rem", Rem),
             ("vunit", Vunit),
+            ("group", Group),
         ];
 
         let symtab = SymbolTable::default();
@@ -4,17 +4,19 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
+use super::attributes::parse_attribute;
 use super::common::error_on_end_identifier_mismatch;
 use super::common::ParseResult;
+use super::context::parse_use_clause;
 use super::declarative_part::parse_declarative_part;
 use super::names::{parse_identifier_list, parse_selected_name};
 use super::range::{parse_array_index_constraint, parse_range};
 use super::subprogram::parse_subprogram_declaration;
 use super::subtype_indication::parse_subtype_indication;
-use super::tokens::{Kind::*, TokenStream};
+use super::tokens::{Kind::Attribute, Kind::*, TokenStream};
 use crate::ast::*;
 use crate::ast::{AbstractLiteral, Range};
-use crate::data::DiagnosticHandler;
+use crate::data::{Diagnostic, DiagnosticHandler};
 
 /// LRM 5.2.2 Enumeration types
 fn parse_enumeration_type_definition(stream: &mut TokenStream) -> ParseResult<TypeDefinition> {
@@ -64,14 +66,22 @@ fn parse_array_index_constraints(stream: &mut TokenStream) -> ParseResult<Vec<Ar
 }
 
 /// LRM 5.3.2 Array types
-fn parse_array_type_definition(stream: &mut TokenStream) -> ParseResult<TypeDefinition> {
+/// Assumes the array keyword has already been consumed
+pub fn parse_array_type_definition_no_semi(
+    stream: &mut TokenStream,
+) -> ParseResult<TypeDefinition> {
     let index_constraints = parse_array_index_constraints(stream)?;
     stream.expect_kind(Of)?;
     let element_subtype = parse_subtype_indication(stream)?;
-    stream.expect_kind(SemiColon)?;
     Ok(TypeDefinition::Array(index_constraints, element_subtype))
 }
 
+fn parse_array_type_definition(stream: &mut TokenStream) -> ParseResult<TypeDefinition> {
+    let def = parse_array_type_definition_no_semi(stream)?;
+    stream.expect_kind(SemiColon)?;
+    Ok(def)
+}
+
 /// LRM 5.3.3 Record types
 fn parse_record_type_definition(
     stream: &mut TokenStream,
@@ -128,6 +138,22 @@ pub fn parse_protected_type_declaration(
             Impure | Function | Procedure => items.push(ProtectedTypeDeclarativeItem::Subprogram(
                 parse_subprogram_declaration(stream, diagnostics)?,
             )),
+            Use => items.push(ProtectedTypeDeclarativeItem::Use(parse_use_clause(stream)?)),
+            Attribute => {
+                for attr in parse_attribute(stream, diagnostics)? {
+                    match attr {
+                        crate::ast::Attribute::Specification(attr_spec) => items.push(
+                            ProtectedTypeDeclarativeItem::AttributeSpecification(attr_spec),
+                        ),
+                        crate::ast::Attribute::Declaration(attr_decl) => diagnostics.push(
+                            Diagnostic::error(
+                                &attr_decl.ident,
+                                "Attribute declaration not allowed in protected type declaration",
+                            ),
+                        ),
+                    }
+                }
+            },
             End => {
                 stream.move_after(&token);
                 break;
@@ -663,6 +689,63 @@ end protected;
         )
     }
 
+    #[test]
+    fn test_protected_type_declaration_with_use_clause_and_attribute_specification() {
+        let code = Code::new(
+            "\
+type foo is protected
+  use work.pkg.all;
+  attribute attr of proc : procedure is 0;
+  procedure proc;
+end protected;
+",
+        );
+        let attr_spec = match code
+            .s1("attribute attr of proc : procedure is 0;")
+            .declarative_part()
+            .pop()
+        {
+            Some(Declaration::Attribute(crate::ast::Attribute::Specification(attr_spec))) => {
+                attr_spec
+            }
+            decl => panic!("Expected attribute specification, got {:?}", decl),
+        };
+        let items = vec![
+            ProtectedTypeDeclarativeItem::Use(code.s1("use work.pkg.all;").use_clause()),
+            ProtectedTypeDeclarativeItem::AttributeSpecification(attr_spec),
+            ProtectedTypeDeclarativeItem::Subprogram(
+                code.s1("procedure proc;")
+                    .s1("procedure proc")
+                    .subprogram_decl(),
+            ),
+        ];
+
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_type_declaration),
+            protected_decl(code.s1("foo").ident(), items)
+        )
+    }
+
+    #[test]
+    fn test_error_on_attribute_declaration_in_protected_type_declaration() {
+        let code = Code::new(
+            "\
+type foo is protected
+  attribute attr : natural;
+end protected;
+",
+        );
+        let (decl, diagnostics) = code.with_stream_diagnostics(parse_type_declaration);
+        assert_eq!(decl, protected_decl(code.s1("foo").ident(), vec![]));
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                code.s("attr", 2),
+                "Attribute declaration not allowed in protected type declaration"
+            )]
+        );
+    }
+
     #[test]
     fn test_protected_type_body() {
         let code = Code::new(
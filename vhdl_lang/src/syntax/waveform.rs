@@ -8,7 +8,13 @@ use crate::ast::{DelayMechanism, Waveform, WaveformElement};
 
 use super::common::{parse_optional, ParseResult};
 use super::expression::parse_expression;
-use super::tokens::{Kind::*, TokenStream};
+use super::tokens::{Kind::*, Token, TokenStream};
+use crate::data::{Diagnostic, Origin};
+
+fn reject_without_inertial(reject_token: &Token) -> Diagnostic {
+    Diagnostic::error(reject_token, "'reject' requires 'inertial' delay")
+        .with_origin(Origin::Semantic)
+}
 
 /// LRM 10.5 Signal assignment statement
 pub fn parse_delay_mechanism(stream: &mut TokenStream) -> ParseResult<Option<DelayMechanism>> {
@@ -16,6 +22,9 @@ pub fn parse_delay_mechanism(stream: &mut TokenStream) -> ParseResult<Option<Del
     match token.kind {
         Transport => {
             stream.move_after(&token);
+            if let Some(reject_token) = stream.pop_if_kind(Reject)? {
+                return Err(reject_without_inertial(&reject_token));
+            }
             Ok(Some(DelayMechanism::Transport))
         }
         Inertial => {
@@ -25,7 +34,9 @@ pub fn parse_delay_mechanism(stream: &mut TokenStream) -> ParseResult<Option<Del
         Reject => {
             stream.move_after(&token);
             let reject = Some(parse_expression(stream)?);
-            stream.expect_kind(Inertial)?;
+            if !stream.skip_if_kind(Inertial)? {
+                return Err(reject_without_inertial(&token));
+            }
             Ok(Some(DelayMechanism::Inertial { reject }))
         }
         _ => Ok(None),
@@ -87,6 +98,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reject_without_inertial_delay_mechanism() {
+        let code = Code::new("reject 2 ns");
+        assert_eq!(
+            code.with_partial_stream(parse_delay_mechanism),
+            Err(
+                Diagnostic::error(code.s1("reject"), "'reject' requires 'inertial' delay")
+                    .with_origin(Origin::Semantic)
+            )
+        );
+    }
+
+    #[test]
+    fn test_transport_reject_delay_mechanism() {
+        let code = Code::new("transport reject 2 ns inertial");
+        assert_eq!(
+            code.with_partial_stream(parse_delay_mechanism),
+            Err(
+                Diagnostic::error(code.s1("reject"), "'reject' requires 'inertial' delay")
+                    .with_origin(Origin::Semantic)
+            )
+        );
+    }
+
     #[test]
     fn test_waveform() {
         let code = Code::new("bar(1 to 3)");
@@ -0,0 +1,68 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2020, Olof Kraigher olof.kraigher@gmail.com
+
+use std::path::Path;
+use vhdl_lang::{analyze_design_file_with_config, Config, Position};
+
+fn installed_config() -> Config {
+    let file_name = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("vhdl_libraries")
+        .join("vhdl_ls.toml");
+    Config::read_file_path(&file_name).unwrap()
+}
+
+#[test]
+fn reports_syntax_and_analysis_errors() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let path = tempdir.path().join("file.vhd");
+    std::fs::write(
+        &path,
+        "
+library ieee;
+use ieee.numeric_std.all;
+
+package pkg is
+  constant a : natural := 0;
+  constant a : unsigned(0 to 1) := \"00\";
+end package;
+
+package pkg2 is
+  constant b t;
+end package;
+",
+    )
+    .unwrap();
+
+    let mut messages = Vec::new();
+    let diagnostics =
+        analyze_design_file_with_config(&path, &installed_config(), &mut messages).unwrap();
+    // Syntax errors come first, names from std and ieee are resolved
+    let got: Vec<_> = diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.pos.start()))
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            ("Expected ':'", Position::new(10, 13)),
+            ("Expected '{identifier}'", Position::new(11, 11)),
+            ("Duplicate declaration of 'a'", Position::new(6, 11)),
+        ]
+    );
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let mut messages = Vec::new();
+    assert!(analyze_design_file_with_config(
+        &tempdir.path().join("missing.vhd"),
+        &Config::default(),
+        &mut messages
+    )
+    .is_err());
+}
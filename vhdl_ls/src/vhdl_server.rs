@@ -520,7 +520,9 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
     lsp_types::Diagnostic {
         range: to_lsp_range(diagnostic.pos.range()),
         severity: Some(severity),
-        code: None,
+        code: diagnostic
+            .code
+            .map(|code| NumberOrString::String(code.to_owned())),
         source: Some("vhdl ls".to_owned()),
         message: diagnostic.message,
         related_information,